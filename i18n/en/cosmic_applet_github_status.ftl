@@ -17,3 +17,166 @@ save = Save
 check-again = Check again
 general-label = General
 poll-interval-label = Poll interval
+open-terminal = Terminal
+terminal-command-label = Terminal command
+terminal-gh-label = Terminal runs
+changes-requested-label = PRs waiting on authors
+track-changes-requested = Track PRs where I requested changes
+badge-palette-label = Badge colours
+reset-defaults = Reset to defaults
+reset-confirm = Reset all settings to their defaults?
+reset-keep-pat = Keep Personal Access Token
+reset = Reset
+cancel = Cancel
+min-age-label = Minimum PR age
+pr-sort-label = Sort PRs by
+badge-accent-label = Use accent colour for badge
+copy-diagnostics = Copy diagnostics
+repo-visibility-label = Repositories
+cached-count = Offline — showing the last known count
+pr-list-limit-label = PRs to list
+more-prs = …and { $count } more
+search-limit-hit = GitHub search only returns the first { $limit } results; narrow your query to see the rest.
+account-enabled-label = Poll this account
+account-paused = Polling is paused for this account
+show-details = Details
+hide-details = Hide details
+clear-repo-filter = { $count } in { $repo } — show all
+daily-progress-label = Show daily progress
+daily-remaining = { $count } remaining
+daily-cleared = Cleared { $cleared } of today's peak of { $peak }
+diagnostics-label = Diagnostics
+last-fetch-duration = Last fetch
+average-fetch-duration = Average of last { $count }
+last-http-status = Last HTTP status
+badge-errors-label = Show errors on badge
+notifications-label = Notify when PRs are waiting
+notify-waiting = { $count } PRs waiting for your review
+notify-while-away = Your review queue changed while away: now { $count }
+pin = Pin
+unpin = Unpin
+pinned = Pinned
+close = Close
+badge-colors-label = Custom badge colours
+badge-color-zero = No PRs
+badge-color-normal = 1–5 PRs
+badge-color-warn = 6–10 PRs
+badge-color-critical = 11+ PRs
+badge-color-error = Error
+badge-color-invalid = { $tier } (invalid, using default)
+use-gh-token-label = Use gh's token
+foreground-interval-label = Poll interval while open
+search-query-label = Search query
+query-preset-label = Preset
+count-history-label = Show count history
+count-history-since = Since { $time }, peak { $peak }
+review-oldest = Review oldest: { $title }
+advanced-label = Advanced
+api-version-label = GitHub API version
+api-version-hint = Sent as X-GitHub-Api-Version; leave empty for the server default.
+badge-error-dot-label = Keep count on errors, marked with a dot
+custom-ca-label = Extra CA certificate (PEM)
+insecure-tls-label = Accept invalid TLS certificates
+insecure-tls-warning = Dangerous: anyone on your network could read your token. Only for self-signed GitHub Enterprise servers.
+pr-new = New
+show-icon-label = Show GitHub icon on panel
+run-test-fetch = Run a test fetch
+test-fetch-count = Fetched count: { $count }
+test-fetch-stats = HTTP status { $status }, { $ms } ms
+extra-metrics-label = Extra panel badges
+extra-metric-label = Badge { $number }
+clear-cache = Clear cache
+time-display-label = Show times as
+updated-at = Updated { $time }
+time-just-now = just now
+time-minutes-ago = { $count } min ago
+time-hours-ago = { $count } h ago
+time-days-ago = { $count } d ago
+working-hours-label = Working hours
+working-hours-enabled = Only poll during working hours
+work-start-label = Start
+work-end-label = End
+outside-working-hours = Outside working hours — showing the last known count
+whats-new-title = What's new
+whats-new-working-hours = Working hours: only poll and notify during your working day.
+whats-new-extra-metrics = Extra panel badges: count up to two more searches on the panel.
+whats-new-time-display = Show times as relative, local clock time or UTC.
+whats-new-review-states = Listed PRs now show their review decision as a coloured dot.
+whats-new-open-settings = Open settings
+dismiss = Dismiss
+managed-notice = Settings are managed by your administrator.
+dim-after-failures-label = Dim icon after repeated failures
+# Digit grouping separator for counts in the popup; use {" "} for a space or {""} for none.
+number-group-separator = ,
+open-config = Open config folder
+accurate-count-label = Count by paging through results (slower, more API calls)
+quiet-start-label = Quiet period after login
+org-filter-label = Organization
+org-filter-placeholder = Any organization
+org-filter-invalid = Not a valid organization name: letters, digits and single hyphens only.
+count-incomplete = GitHub's search timed out; the count may be too low.
+saved = Saved
+copied = Copied to clipboard
+config-save-failed = Couldn't save settings: { $error }
+open-failed = Couldn't open the browser: { $error }
+row-fields-label = PR list rows
+row-field-title = Title
+row-field-repo = Repository
+row-field-author = Author
+row-field-age = Age
+row-field-review-state = Review decision
+exclude-own-label = Leave out my own PRs
+reload-config = Reload config
+config-reloaded = Config reloaded
+badge-aggregate-label = Sum them up on the main badge
+badge-aggregate-breakdown = Included in the badge
+badge-aggregate-total = Total
+autohide-label = Hide from panel while there's nothing to review
+tabs-label = Popup tabs
+tab-main = Main
+tab-name-placeholder = Tab name, e.g. Urgent
+add-tab = Add tab
+remove = Remove
+badge-sums-tabs-label = Add the tabs' counts to the badge
+realtime-url-label = Realtime relay (Server-Sent Events)
+realtime-url-hint = Each event from the relay triggers a refresh; polling continues as a fallback. Leave empty to only poll.
+realtime-url-invalid = The relay URL must start with http:// or https://
+label-format-label = Panel text without the icon
+label-format-hint = {"{"}count{"}"} is replaced with the number, e.g. "PR:{"{"}count{"}"}" or "{"{"}count{"}"} reviews".
+label-format-invalid = The panel text must contain {"{"}count{"}"}
+auto-open-label = Open GitHub when the queue reaches
+conflicts-label = My PRs with merge conflicts
+open-authored = Open my PRs
+track-conflicts = Track merge conflicts in my PRs
+all-time-peak = All-time peak
+reset-peak = Reset
+dim-during-dnd-label = Dim icon during Do Not Disturb
+token-command-label = Token command
+token-command-hint = A shell command that prints the token, e.g. to read it from a secret store. When set, it is used instead of the token above.
+pr-earlier = Earlier
+http1-only-label = Use HTTP/1.1 only
+no-connection-reuse-label = Don't reuse connections
+comparison-label = Compare with
+comparison-hint = Shown next to the main count in the popup, e.g. reviews done today. {"{"}today{"}"} stands for today's date. Leave empty to turn off.
+comparison-summary = { $pending } pending / { $done } done
+project-label = Project
+project-hint = URL or node ID of a GitHub Projects board; the popup counts its items assigned to you. Leave empty to turn off.
+project-ignored-status-label = Leave out items with status
+project-count-label = Project items assigned to me
+open-project = Open project
+outputs-label = Only on these outputs
+outputs-placeholder = All outputs
+outputs-hint = Comma-separated, e.g. "DP-1, eDP-1". This panel is on { $output }.
+badge-color-unknown = Outdated
+stale-after-label = Distrust the count after
+count-stale = No successful update for a long time; this count is too old to trust.
+curl-command = Equivalent curl command
+copy-curl-command = Copy
+row-field-ci = Link to failing CI
+open-ci = Open CI
+animate-count-label = Pulse badge when the count changes
+count-click-label = Clicking the count
+include-approved-label = Keep counting approved PRs
+token-expires-in = Token expires in { $days } days; renew it on GitHub before it stops working.
+token-expired = Token has expired; create a new one on GitHub.
+pr-status-error = Review and CI markers could not be updated ({ $error })
@@ -1,115 +1,864 @@
 // SPDX-License-Identifier: GPL-3.0
 
-use crate::config::{AuthMethod, Config};
+use crate::cache;
+use crate::config::{
+    AuthMethod, BadgePalette, Config, CountClickAction, NotificationsConfig, PopupTab, PrSort,
+    RepoVisibility, State, TerminalGhCommand, TimeDisplay,
+};
+use crate::diagnostics;
 use crate::fl;
+use crate::github::{
+    self, ErrorKind, FetchError, FetchStats, PrStatus, PullRequest, ReviewState, SearchRequest,
+    SearchResults, TlsOptions, AUTHORED_QUERY, CHANGES_REQUESTED_QUERY, SEARCH_QUERY,
+};
+use crate::realtime;
+use crate::systemd;
 use cosmic::cosmic_config::{self, CosmicConfigEntry};
-use cosmic::iced::{window::Id, Alignment, Limits, Subscription};
+use cosmic::iced::{window::Id, Alignment, Color, Limits, Subscription};
 use cosmic::iced_winit::commands::popup::{destroy_popup, get_popup};
 use cosmic::prelude::*;
-use cosmic::widget;
+use cosmic::widget::{self, segmented_button};
 use futures_util::SinkExt;
-use std::time::Duration;
-
-const GITHUB_REVIEW_URL: &str = "https://github.com/pulls?q=is%3Apr+is%3Aopen+review-requested%3A%40me+-review%3Aapproved";
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant, SystemTime};
 
 const POLL_LABELS: &[&str] = &["30 sec", "1 min", "2 min", "5 min", "10 min", "30 min"];
 const POLL_VALUES: &[u64] = &[30, 60, 120, 300, 600, 1800];
 
-async fn fetch_pr_count(auth_method: AuthMethod, pat: String) -> Result<u32, String> {
-    match auth_method {
-        AuthMethod::GhCli => fetch_via_gh_cli().await,
-        AuthMethod::Pat => {
-            if pat.is_empty() {
-                return Err("No PAT configured. Open Settings to add one.".to_string());
+const FOREGROUND_POLL_LABELS: &[&str] =
+    &["Same as closed", "10 sec", "30 sec", "1 min", "2 min", "5 min"];
+const FOREGROUND_POLL_VALUES: &[u64] = &[0, 10, 30, 60, 120, 300];
+
+const QUERY_PRESET_LABELS: &[&str] =
+    &["Reviews requested", "Assigned to me", "My open PRs", "Mentioned"];
+const QUERY_PRESET_VALUES: &[&str] = &[
+    SEARCH_QUERY,
+    "is:pr is:open assignee:@me",
+    "is:pr is:open author:@me",
+    "is:pr is:open mentions:@me",
+];
+
+/// "None" followed by [`QUERY_PRESET_LABELS`].
+const EXTRA_METRIC_LABELS: &[&str] =
+    &["None", "Reviews requested", "Assigned to me", "My open PRs", "Mentioned"];
+
+const MIN_AGE_LABELS: &[&str] = &["Off", "1 hour", "4 hours", "8 hours", "1 day", "2 days", "1 week"];
+const MIN_AGE_VALUES: &[u32] = &[0, 1, 4, 8, 24, 48, 168];
+
+const VISIBILITY_LABELS: &[&str] = &["All repositories", "Public only", "Private only"];
+const VISIBILITY_VALUES: &[RepoVisibility] = &[
+    RepoVisibility::All,
+    RepoVisibility::PublicOnly,
+    RepoVisibility::PrivateOnly,
+];
+
+const SORT_LABELS: &[&str] = &["Best match", "Newest", "Oldest", "Repository"];
+const SORT_VALUES: &[PrSort] = &[PrSort::Default, PrSort::Newest, PrSort::Oldest, PrSort::Repo];
+
+const LIST_LIMIT_LABELS: &[&str] = &["3", "5", "10", "20", "50", "100", "200"];
+const LIST_LIMIT_VALUES: &[usize] = &[3, 5, 10, 20, 50, 100, 200];
+
+const TIME_DISPLAY_LABELS: &[&str] = &["Relative", "Local time", "UTC"];
+const TIME_DISPLAY_VALUES: &[TimeDisplay] =
+    &[TimeDisplay::Relative, TimeDisplay::LocalClock, TimeDisplay::Utc];
+
+const TERMINAL_GH_LABELS: &[&str] = &["Search review queue", "gh pr status"];
+const TERMINAL_GH_VALUES: &[TerminalGhCommand] =
+    &[TerminalGhCommand::SearchPrs, TerminalGhCommand::PrStatus];
+
+const COUNT_CLICK_LABELS: &[&str] = &["Open GitHub", "Open oldest PR", "Nothing"];
+const COUNT_CLICK_VALUES: &[CountClickAction] = &[
+    CountClickAction::OpenGitHub,
+    CountClickAction::OpenOldest,
+    CountClickAction::Nothing,
+];
+
+/// Index is the hour.
+const HOUR_LABELS: &[&str] = &[
+    "00:00", "01:00", "02:00", "03:00", "04:00", "05:00", "06:00", "07:00", "08:00", "09:00",
+    "10:00", "11:00", "12:00", "13:00", "14:00", "15:00", "16:00", "17:00", "18:00", "19:00",
+    "20:00", "21:00", "22:00", "23:00",
+];
+
+const WORK_DAYS: &[(chrono::Weekday, &str)] = &[
+    (chrono::Weekday::Mon, "Mon"),
+    (chrono::Weekday::Tue, "Tue"),
+    (chrono::Weekday::Wed, "Wed"),
+    (chrono::Weekday::Thu, "Thu"),
+    (chrono::Weekday::Fri, "Fri"),
+    (chrono::Weekday::Sat, "Sat"),
+    (chrono::Weekday::Sun, "Sun"),
+];
+
+const DIM_AFTER_LABELS: &[&str] =
+    &["Never", "2 failures", "3 failures", "5 failures", "10 failures"];
+const DIM_AFTER_VALUES: &[u32] = &[0, 2, 3, 5, 10];
+
+const AUTO_OPEN_LABELS: &[&str] = &["Never", "3 PRs", "5 PRs", "10 PRs", "20 PRs"];
+const AUTO_OPEN_VALUES: &[Option<u32>] = &[None, Some(3), Some(5), Some(10), Some(20)];
+
+/// Settings sections listed beside the page, in page order.
+const SETTINGS_SECTION_LABELS: &[&str] = &[
+    "Account",
+    "Search",
+    "General",
+    "Working hours",
+    "Extra badges",
+    "Project",
+    "Tabs",
+    "PR list",
+    "Badge colours",
+    "Advanced",
+    "Diagnostics",
+    "Reset",
+];
+
+const STALE_AFTER_LABELS: &[&str] = &["Never", "15 min", "30 min", "1 hour", "2 hours"];
+const STALE_AFTER_VALUES: &[u64] = &[0, 900, 1800, 3600, 7200];
+
+const QUIET_START_LABELS: &[&str] = &["Off", "30 sec", "1 min", "2 min", "5 min"];
+const QUIET_START_VALUES: &[u64] = &[0, 30, 60, 120, 300];
+
+const PALETTE_LABELS: &[&str] = &["Follow system", "Standard", "High contrast"];
+const PALETTE_VALUES: &[BadgePalette] = &[
+    BadgePalette::Auto,
+    BadgePalette::Standard,
+    BadgePalette::HighContrast,
+];
+
+/// Urgency tier of the panel badge, which determines its colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BadgeTier {
+    Error,
+    /// The count is too old to trust.
+    Unknown,
+    Zero,
+    Normal,
+    Warn,
+    Critical,
+}
+
+impl BadgeTier {
+    /// Badge background for this tier. The high-contrast shades are dark enough to keep
+    /// the white label above a 4.5:1 contrast ratio.
+    fn color(self, high_contrast: bool) -> Color {
+        match (self, high_contrast) {
+            (Self::Error | Self::Critical, false) => Color::from_rgb(0.82, 0.18, 0.18),
+            (Self::Zero, false) => Color::from_rgb(0.13, 0.65, 0.30),
+            (Self::Normal, false) => Color::from_rgb(0.15, 0.45, 0.85),
+            (Self::Warn, false) => Color::from_rgb(0.80, 0.65, 0.10),
+            (Self::Unknown, false) => Color::from_rgb(0.5, 0.5, 0.5),
+            (Self::Error | Self::Critical, true) => Color::from_rgb(0.62, 0.0, 0.0),
+            (Self::Zero, true) => Color::from_rgb(0.0, 0.40, 0.12),
+            (Self::Normal, true) => Color::from_rgb(0.0, 0.25, 0.62),
+            (Self::Warn, true) => Color::from_rgb(0.55, 0.30, 0.0),
+            (Self::Unknown, true) => Color::from_rgb(0.30, 0.30, 0.30),
+        }
+    }
+
+    /// The user's custom colour for this tier, as entered in settings.
+    fn custom_color(self, config: &Config) -> &str {
+        match self {
+            Self::Error => &config.badge_color_error,
+            Self::Zero => &config.badge_color_zero,
+            Self::Normal => &config.badge_color_normal,
+            Self::Warn => &config.badge_color_warn,
+            Self::Critical => &config.badge_color_critical,
+            Self::Unknown => &config.badge_color_unknown,
+        }
+    }
+
+    /// Tier for a successfully fetched count.
+    fn for_count(count: u64) -> Self {
+        match count {
+            0 => Self::Zero,
+            1..=5 => Self::Normal,
+            6..=10 => Self::Warn,
+            _ => Self::Critical,
+        }
+    }
+
+    /// Name of the tier in the badge colour settings.
+    fn label(self) -> String {
+        match self {
+            Self::Error => fl!("badge-color-error"),
+            Self::Zero => fl!("badge-color-zero"),
+            Self::Normal => fl!("badge-color-normal"),
+            Self::Warn => fl!("badge-color-warn"),
+            Self::Critical => fl!("badge-color-critical"),
+            Self::Unknown => fl!("badge-color-unknown"),
+        }
+    }
+}
+
+/// Tiers in the order their custom colours are listed in settings.
+const COLOR_TIERS: &[BadgeTier] = &[
+    BadgeTier::Zero,
+    BadgeTier::Normal,
+    BadgeTier::Warn,
+    BadgeTier::Critical,
+    BadgeTier::Error,
+    BadgeTier::Unknown,
+];
+
+/// Optional part of a PR list row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowField {
+    Title,
+    Repo,
+    Author,
+    Age,
+    ReviewState,
+    Ci,
+}
+
+impl RowField {
+    fn label(self) -> String {
+        match self {
+            Self::Title => fl!("row-field-title"),
+            Self::Repo => fl!("row-field-repo"),
+            Self::Author => fl!("row-field-author"),
+            Self::Age => fl!("row-field-age"),
+            Self::ReviewState => fl!("row-field-review-state"),
+            Self::Ci => fl!("row-field-ci"),
+        }
+    }
+
+    /// The config flag that turns this field on.
+    fn flag(self, config: &mut Config) -> &mut bool {
+        match self {
+            Self::Title => &mut config.row_show_title,
+            Self::Repo => &mut config.row_show_repo,
+            Self::Author => &mut config.row_show_author,
+            Self::Age => &mut config.row_show_age,
+            Self::ReviewState => &mut config.row_show_review_state,
+            Self::Ci => &mut config.row_show_ci,
+        }
+    }
+
+    fn enabled(self, config: &Config) -> bool {
+        match self {
+            Self::Title => config.row_show_title,
+            Self::Repo => config.row_show_repo,
+            Self::Author => config.row_show_author,
+            Self::Age => config.row_show_age,
+            Self::ReviewState => config.row_show_review_state,
+            Self::Ci => config.row_show_ci,
+        }
+    }
+}
+
+/// Row fields in the order they're listed in settings.
+const ROW_FIELDS: &[RowField] = &[
+    RowField::Title,
+    RowField::Repo,
+    RowField::Author,
+    RowField::Age,
+    RowField::ReviewState,
+    RowField::Ci,
+];
+
+/// Marker colour for a PR's review decision, borrowed from the badge tiers.
+fn review_state_color(state: ReviewState, high_contrast: bool) -> Color {
+    match state {
+        ReviewState::Approved => BadgeTier::Zero.color(high_contrast),
+        ReviewState::ChangesRequested => BadgeTier::Critical.color(high_contrast),
+        ReviewState::Pending => BadgeTier::Warn.color(high_contrast),
+    }
+}
+
+/// Label and tier of the panel badge, if it shows anything. With error badges turned off,
+/// errors only show in the popup and the last known count stays. In dot mode the last
+/// known count stays too, and [`shows_error_dot`] marks the failed refresh. A `stale`
+/// count is replaced by a "?".
+fn compute_badge(
+    error: Option<&FetchError>,
+    count: Option<u64>,
+    stale: bool,
+    config: &Config,
+) -> Option<(String, BadgeTier)> {
+    match (error, count) {
+        (Some(_), _) if config.badge_errors && !shows_error_dot(error, count, config) => {
+            Some(("!".into(), BadgeTier::Error))
+        }
+        (_, Some(_)) if stale => Some(("?".into(), BadgeTier::Unknown)),
+        (_, Some(n)) => Some((badge_label(n), BadgeTier::for_count(n))),
+        (_, None) => None,
+    }
+}
+
+/// Whether a failed refresh is marked with a dot next to the last known count.
+fn shows_error_dot(error: Option<&FetchError>, count: Option<u64>, config: &Config) -> bool {
+    config.badge_errors && config.badge_error_dot && error.is_some() && count.is_some()
+}
+
+/// Panel text for a count in compact mode. A format without `{count}` would hide the count,
+/// so it's ignored.
+fn format_label(format: &str, label: &str) -> String {
+    if format.contains("{count}") {
+        format.replace("{count}", label)
+    } else {
+        label.to_string()
+    }
+}
+
+/// Lines of an error message shown before it is cut off behind "Show details".
+const ERROR_PREVIEW_LINES: usize = 3;
+
+/// Characters per line of an error message shown before it is cut off.
+const ERROR_PREVIEW_LINE_LEN: usize = 120;
+
+/// The start of a possibly huge error message, and whether anything was cut off.
+fn error_preview(message: &str) -> (String, bool) {
+    let mut truncated = message.lines().count() > ERROR_PREVIEW_LINES;
+    let lines: Vec<String> = message
+        .lines()
+        .take(ERROR_PREVIEW_LINES)
+        .map(|line| match line.char_indices().nth(ERROR_PREVIEW_LINE_LEN) {
+            Some((end, _)) => {
+                truncated = true;
+                format!("{}…", &line[..end])
             }
-            fetch_via_pat(&pat).await
+            None => line.to_string(),
+        })
+        .collect();
+    (lines.join("\n"), truncated)
+}
+
+/// Room kept free on the right of scrolled content for the scrollbar.
+const SCROLLBAR_GUTTER: u16 = 8;
+
+/// The scrolling part of a popup page, below the header that stays in place. The content
+/// keeps clear of the scrollbar instead of running underneath it.
+fn scrolled<'a>(content: impl Into<Element<'a, Message>>) -> Element<'a, Message> {
+    widget::scrollable(widget::container(content).padding([0, SCROLLBAR_GUTTER, 0, 0])).into()
+}
+
+/// Text that may hold long unbroken tokens (URLs, JSON); wraps anywhere if it has to, so
+/// it never widens the popup.
+fn wrapped_text<'a>(text: String, size: u16) -> Element<'a, Message> {
+    widget::text(text)
+        .size(size)
+        .wrapping(cosmic::iced::widget::text::Wrapping::WordOrGlyph)
+        .into()
+}
+
+/// Largest count the panel badge shows in full; bigger ones are abbreviated.
+const MAX_BADGE_COUNT: u64 = 999;
+
+/// Panel label for a count, clamped to fit the small badge.
+fn badge_label(count: u64) -> String {
+    if count > MAX_BADGE_COUNT {
+        format!("{MAX_BADGE_COUNT}+")
+    } else {
+        count.to_string()
+    }
+}
+
+/// Popup text for a count, with the digits grouped the way the current language does it
+/// ("12,345"). The badge keeps its compact [`badge_label`].
+fn format_count(count: u64) -> String {
+    let digits = count.to_string();
+    let separator = fl!("number-group-separator");
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3 * separator.len());
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            formatted.push_str(&separator);
         }
+        formatted.push(digit);
     }
+    formatted
 }
 
-async fn fetch_via_gh_cli() -> Result<u32, String> {
-    let output = tokio::process::Command::new("gh")
-        .args([
-            "api",
-            "search/issues",
-            "--method", "GET",
-            "-f", "q=is:pr is:open review-requested:@me -review:approved",
-            "--jq", ".total_count",
-        ])
-        .output()
-        .await
-        .map_err(|e| format!("gh not found: {e}"))?;
+/// Filled, fully rounded background in `color`, for badges and pills.
+fn badge_style(color: Color) -> cosmic::theme::Container<'static> {
+    cosmic::theme::Container::Custom(Box::new(move |_| cosmic::iced_widget::container::Style {
+        background: Some(cosmic::iced::Background::Color(color)),
+        border: cosmic::iced::Border {
+            radius: 100.0.into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }))
+}
+
+/// A count on its own, as shown on the panel without the icon.
+fn count_pill<'a>(label: String, color: Color) -> Element<'a, Message> {
+    widget::container(widget::text(label).size(12).class(Color::WHITE))
+        .padding([1, 6])
+        .class(badge_style(color))
+        .into()
+}
+
+/// Parses a `#rrggbb` colour; the leading `#` is optional.
+fn parse_hex_color(hex: &str) -> Option<Color> {
+    let hex = hex.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::from_rgb8(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Back button of the settings page, focused when the page opens.
+static SETTINGS_BACK_ID: LazyLock<widget::Id> = LazyLock::new(|| widget::Id::new("settings-back"));
+
+/// Scrolling part of the settings page, moved by the section list.
+static SETTINGS_SCROLL_ID: LazyLock<widget::Id> =
+    LazyLock::new(|| widget::Id::new("settings-scroll"));
+
+/// One per entry of `SETTINGS_SECTION_LABELS`, on the container holding that section.
+static SETTINGS_SECTION_IDS: LazyLock<Vec<widget::Id>> = LazyLock::new(|| {
+    (0..SETTINGS_SECTION_LABELS.len())
+        .map(|idx| widget::Id::new(format!("settings-section-{idx}")))
+        .collect()
+});
+
+/// Popup width while the settings page, with its section list, is shown.
+const SETTINGS_POPUP_WIDTH: f32 = 480.0;
+
+/// Width of the section list on the settings page.
+const SETTINGS_NAV_WIDTH: f32 = 130.0;
+
+/// Measures how far down the settings page a section starts, so it can be scrolled to
+/// the top of the view.
+struct SectionOffset {
+    target: widget::Id,
+    content_top: Option<f32>,
+    offset: Option<f32>,
+}
+
+impl SectionOffset {
+    fn new(section: usize) -> Self {
+        Self {
+            target: SETTINGS_SECTION_IDS[section].clone(),
+            content_top: None,
+            offset: None,
+        }
+    }
+}
 
-    if !output.status.success() {
-        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+impl cosmic::iced::advanced::widget::Operation<f32> for SectionOffset {
+    fn container(
+        &mut self,
+        id: Option<&widget::Id>,
+        bounds: cosmic::iced::Rectangle,
+        operate_on_children: &mut dyn FnMut(
+            &mut dyn cosmic::iced::advanced::widget::Operation<f32>,
+        ),
+    ) {
+        if id == Some(&self.target) {
+            // Layout bounds ignore the scroll position, so this is the distance from the
+            // top of the scrolled content.
+            self.offset = self.content_top.map(|top| bounds.y - top);
+            return;
+        }
+        operate_on_children(self);
+    }
+
+    fn scrollable(
+        &mut self,
+        _state: &mut dyn cosmic::iced::advanced::widget::operation::Scrollable,
+        id: Option<&widget::Id>,
+        _bounds: cosmic::iced::Rectangle,
+        content_bounds: cosmic::iced::Rectangle,
+        _translation: cosmic::iced::Vector,
+    ) {
+        if id == Some(&*SETTINGS_SCROLL_ID) {
+            self.content_top = Some(content_bounds.y);
+        }
     }
 
-    String::from_utf8_lossy(&output.stdout)
-        .trim()
-        .parse::<u32>()
-        .map_err(|e| e.to_string())
+    fn finish(&self) -> cosmic::iced::advanced::widget::operation::Outcome<f32> {
+        use cosmic::iced::advanced::widget::operation::Outcome;
+        self.offset.map_or(Outcome::None, Outcome::Some)
+    }
+}
+
+/// Copy of the installed app icon, used when the icon theme can't provide it.
+const FALLBACK_ICON: &[u8] = include_bytes!("../resources/icon.svg");
+
+/// Whether the icon theme resolves the app icon; looked up once, as it means a disk scan.
+static NAMED_ICON_FOUND: LazyLock<bool> = LazyLock::new(|| {
+    widget::icon::from_name(<AppModel as cosmic::Application>::APP_ID)
+        .path()
+        .is_some()
+});
+
+/// The panel icon: the themed app icon if installed, otherwise the bundled copy, so the
+/// applet never disappears from the panel.
+fn panel_icon() -> widget::icon::Handle {
+    if *NAMED_ICON_FOUND {
+        widget::icon::from_name(<AppModel as cosmic::Application>::APP_ID).handle()
+    } else {
+        widget::icon::from_svg_bytes(FALLBACK_ICON)
+    }
 }
 
-async fn fetch_via_pat(pat: &str) -> Result<u32, String> {
-    let output = tokio::process::Command::new("curl")
+/// Shows a desktop notification through `notify-send`.
+fn send_notification(summary: &str, body: &str) {
+    let _ = std::process::Command::new("notify-send")
         .args([
-            "--silent",
-            "-H", &format!("Authorization: Bearer {pat}"),
-            "-H", "Accept: application/vnd.github+json",
-            "https://api.github.com/search/issues?q=is:pr+is:open+review-requested:@me+-review:approved",
+            "--app-name",
+            "GitHub Status",
+            "--icon",
+            <AppModel as cosmic::Application>::APP_ID,
+            summary,
+            body,
         ])
-        .output()
-        .await
-        .map_err(|e| format!("curl not found: {e}"))?;
+        .spawn();
+}
 
-    if !output.status.success() {
-        return Err(format!(
-            "Request failed: {}",
-            String::from_utf8_lossy(&output.stderr)
-        ));
-    }
+/// Opens the configured terminal running `gh`, then drops into an interactive shell so the
+/// output stays on screen.
+fn open_in_terminal(terminal_command: &str, gh_args: &[String]) {
+    let mut parts = terminal_command.split_whitespace();
+    let Some(program) = parts.next() else {
+        return;
+    };
+
+    // The gh arguments are positional parameters, so none of them is parsed by the shell.
+    let script = "gh \"$@\"; exec \"${SHELL:-sh}\"";
+    let _ = std::process::Command::new(program)
+        .args(parts)
+        .args(["sh", "-c", script, "sh"])
+        .args(gh_args)
+        .spawn();
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let value: serde_json::Value =
-        serde_json::from_str(&stdout).map_err(|e| format!("JSON parse error: {e}"))?;
+/// Arguments for the `gh` command the terminal runs.
+///
+/// The search query goes in word by word: gh quotes a keyword containing spaces, which
+/// would turn the whole query into one literal phrase instead of its qualifiers.
+fn terminal_gh_args(command: TerminalGhCommand, query: &str, repo: Option<&str>) -> Vec<String> {
+    let (base, rest): (&[&str], Vec<&str>) = match command {
+        TerminalGhCommand::SearchPrs => {
+            (&["search", "prs", "--"], query.split_whitespace().collect())
+        }
+        TerminalGhCommand::PrStatus => {
+            (&["pr", "status"], repo.map(|repo| vec!["--repo", repo]).unwrap_or_default())
+        }
+    };
+    base.iter().copied().chain(rest).map(str::to_string).collect()
+}
 
-    value["total_count"].as_u64().map(|n| n as u32).ok_or_else(|| {
-        value["message"]
-            .as_str()
-            .map(|m| format!("API error: {m}"))
-            .unwrap_or_else(|| "total_count not found in response".to_string())
-    })
+/// Text shown in the CA certificate path input.
+fn ca_path_text(config: &Config) -> String {
+    config
+        .custom_ca_path
+        .as_ref()
+        .map(|path| path.display().to_string())
+        .unwrap_or_default()
 }
 
-async fn check_gh_status() -> Result<String, String> {
-    let output = tokio::process::Command::new("gh")
-        .args(["auth", "status"])
-        .output()
-        .await
-        .map_err(|_| "gh not found or not executable".to_string())?;
+/// Relative times beyond this many days are shown as dates instead.
+const MAX_RELATIVE_DAYS: i64 = 365;
+
+/// Locale for absolute dates, from `LC_ALL`, `LC_TIME` or `LANG` like the C library picks
+/// it. `None` when unset, `C`/`POSIX`, or not a locale chrono knows, and dates stay ISO.
+static DATE_LOCALE: LazyLock<Option<chrono::Locale>> = LazyLock::new(|| {
+    let name = ["LC_ALL", "LC_TIME", "LANG"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())?;
+    // Drop the codeset and modifier: "de_DE.UTF-8@euro" -> "de_DE".
+    let name = name.split(['.', '@']).next().unwrap_or_default();
+    if name == "C" || name == "POSIX" {
+        return None;
+    }
+    chrono::Locale::try_from(name).ok()
+});
 
-    // gh auth status writes to stderr
-    let text = format!(
-        "{}{}",
-        String::from_utf8_lossy(&output.stdout),
-        String::from_utf8_lossy(&output.stderr)
-    );
+/// Formats the date and clock time of `time`, the date in the locale's short form.
+fn format_date_time<Tz: chrono::TimeZone>(
+    time: &chrono::DateTime<Tz>,
+    locale: Option<chrono::Locale>,
+) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    match locale {
+        Some(locale) => time.format_localized("%x %H:%M", locale).to_string(),
+        None => time.format("%Y-%m-%d %H:%M").to_string(),
+    }
+}
 
-    for line in text.lines() {
-        if line.contains("Logged in to") && line.contains("account") {
-            if let Some(pos) = line.find("account ") {
-                let rest = &line[pos + 8..];
-                let username = rest.split_whitespace().next().unwrap_or("unknown");
-                return Ok(username.to_string());
+/// Formats a timestamp for the popup. Absolute times leave out the date when it is today.
+///
+/// Relative times survive clock steps: a timestamp in the future (the clock went back)
+/// reads "just now", and one implausibly far in the past (the clock jumped ahead) falls
+/// back to the local clock time.
+fn format_time(time: chrono::DateTime<chrono::Utc>, mode: TimeDisplay) -> String {
+    match mode {
+        TimeDisplay::Relative => {
+            let elapsed = chrono::Utc::now().signed_duration_since(time);
+            if elapsed.num_days() > MAX_RELATIVE_DAYS {
+                return format_time(time, TimeDisplay::LocalClock);
+            }
+            match elapsed.num_minutes() {
+                ..1 => fl!("time-just-now"),
+                minutes @ 1..60 => fl!("time-minutes-ago", count = minutes),
+                _ if elapsed.num_hours() < 24 => fl!("time-hours-ago", count = elapsed.num_hours()),
+                _ => fl!("time-days-ago", count = elapsed.num_days()),
+            }
+        }
+        TimeDisplay::LocalClock => {
+            let local = time.with_timezone(&chrono::Local);
+            if local.date_naive() == chrono::Local::now().date_naive() {
+                local.format("%H:%M").to_string()
+            } else {
+                format_date_time(&local, *DATE_LOCALE)
             }
         }
+        TimeDisplay::Utc => {
+            if time.date_naive() == chrono::Utc::now().date_naive() {
+                time.format("%H:%M UTC").to_string()
+            } else {
+                format!("{} UTC", format_date_time(&time, *DATE_LOCALE))
+            }
+        }
+    }
+}
+
+/// Appends the qualifiers for the user's search filters to a base query.
+fn filtered_query(base: &str, config: &Config) -> String {
+    let mut query = base.to_string();
+    match config.repo_visibility {
+        RepoVisibility::All => {}
+        RepoVisibility::PublicOnly => query.push_str(" is:public"),
+        RepoVisibility::PrivateOnly => query.push_str(" is:private"),
+    }
+    if let Some(org) = &config.org_filter {
+        query.push_str(&format!(" org:{org}"));
+    }
+    query
+}
+
+/// The search behind the badge, with the filters from settings applied.
+fn main_query(config: &Config) -> String {
+    let base = if config.include_approved {
+        config
+            .search_query
+            .split_whitespace()
+            .filter(|term| *term != "-review:approved")
+            .collect::<Vec<_>>()
+            .join(" ")
+    } else {
+        config.search_query.clone()
+    };
+    let mut query = filtered_query(&base, config);
+    // Only for the main search: extra badges may well be about my own PRs.
+    if config.exclude_own {
+        query.push_str(" -author:@me");
+    }
+    query
+}
+
+/// The comparison search with `{today}` filled in, if one is set.
+fn comparison_query(config: &Config) -> Option<String> {
+    if config.comparison_query.is_empty() {
+        return None;
+    }
+    let today = chrono::Local::now().date_naive().to_string();
+    Some(config.comparison_query.replace("{today}", &today))
+}
+
+/// Whether `name` could be a GitHub organization: up to 39 letters, digits and single
+/// hyphens, not starting or ending with a hyphen.
+fn is_valid_org(name: &str) -> bool {
+    (1..=39).contains(&name.len())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+        && !name.contains("--")
+}
+
+/// Connection settings for the PAT path.
+fn tls_options(config: &Config) -> TlsOptions {
+    TlsOptions {
+        allow_insecure: config.allow_insecure_tls,
+        custom_ca_path: config.custom_ca_path.clone(),
+        http1_only: config.http1_only,
+        no_connection_reuse: config.no_connection_reuse,
+    }
+}
+
+/// The search behind the panel badge and PR list.
+pub fn review_search(config: &Config) -> SearchRequest {
+    list_search(config, main_query(config))
+}
+
+/// A search whose PRs are listed in the popup as well as counted.
+fn list_search(config: &Config, query: String) -> SearchRequest {
+    // An accurate count pages through every result the search API will return.
+    let max_items = if config.accurate_count {
+        github::SEARCH_RESULT_LIMIT
+    } else {
+        u32::try_from(config.pr_list_limit).unwrap_or(u32::MAX)
+    };
+
+    SearchRequest {
+        auth_method: config.auth_method,
+        pat: config.github_pat.clone(),
+        use_gh_token: config.use_gh_token,
+        token_command: config.token_command.clone(),
+        auth_backend: config.auth_backend.clone(),
+        api_version: config.api_version.clone(),
+        tls: tls_options(config),
+        query,
+        max_items,
+        min_age_hours: config.min_age_hours,
+        sort: config.pr_sort,
+        accurate_count: config.accurate_count,
     }
+}
 
-    if !output.status.success() {
-        return Err("Not logged in. Run: gh auth login".to_string());
+/// A search of which only the total count is needed.
+fn count_search(config: &Config, base_query: &str) -> SearchRequest {
+    SearchRequest {
+        auth_method: config.auth_method,
+        pat: config.github_pat.clone(),
+        use_gh_token: config.use_gh_token,
+        token_command: config.token_command.clone(),
+        auth_backend: config.auth_backend.clone(),
+        api_version: config.api_version.clone(),
+        tls: tls_options(config),
+        query: filtered_query(base_query, config),
+        max_items: 1,
+        min_age_hours: 0,
+        sort: PrSort::Default,
+        accurate_count: false,
     }
+}
+
+/// Number of recent fetch durations averaged in the diagnostics section.
+const FETCH_HISTORY_LEN: usize = 10;
+
+/// How long the popup must stay open before the listed PRs stop being marked as new.
+const MARK_SEEN_DELAY: Duration = Duration::from_secs(3);
+
+/// Most extra metrics shown on the panel next to the main badge.
+const MAX_EXTRA_METRICS: usize = 2;
+
+/// Number of counts kept for the history sparkline, one per successful poll.
+const COUNT_HISTORY_LEN: usize = 60;
+
+/// Cache file holding the count history, so the sparkline survives restarts.
+const COUNT_HISTORY_CACHE_KEY: &str = "count-history.json";
+
+/// Height of the history sparkline, in pixels.
+const SPARKLINE_HEIGHT: f32 = 32.0;
+
+/// How often a waiting poller checks whether the machine was suspended.
+const WAKE_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Wait before reconnecting to the realtime relay after the stream ends or fails.
+const REALTIME_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// How long a toast stays at the bottom of the popup.
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+/// Wait before fetching again after GitHub flagged a search as incomplete.
+const INCOMPLETE_RETRY_DELAY: Duration = Duration::from_secs(15);
+
+/// Shortest time between two automatic openings of the review queue, so a count hovering
+/// around the threshold doesn't keep opening tabs.
+const AUTO_OPEN_COOLDOWN: Duration = Duration::from_secs(60 * 60);
 
-    Ok("Connected".to_string())
+/// Days before the PAT expires that the settings start warning about it.
+const TOKEN_EXPIRY_WARNING_DAYS: i64 = 7;
+
+/// How long the badge lights up after the count changes.
+const COUNT_PULSE: Duration = Duration::from_millis(600);
+
+/// Whether the desktop asks for animations, from GNOME's `enable-animations` interface
+/// setting read through `gsettings`; COSMIC has no setting of its own for this. Animations
+/// stay on when it can't be read.
+async fn animations_enabled() -> bool {
+    tokio::process::Command::new("gsettings")
+        .args(["get", "org.gnome.desktop.interface", "enable-animations"])
+        .output()
+        .await
+        .map_or(true, |output| String::from_utf8_lossy(&output.stdout).trim() != "false")
+}
+
+/// Running applet version, compared against the last dismissed "What's new" notes.
+const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// "What's new" items with the version that introduced them, oldest first.
+fn release_notes() -> [(&'static str, String); 4] {
+    [
+        ("1.1.0", fl!("whats-new-working-hours")),
+        ("1.1.0", fl!("whats-new-extra-metrics")),
+        ("1.1.0", fl!("whats-new-time-display")),
+        ("1.1.0", fl!("whats-new-review-states")),
+    ]
+}
+
+/// Whether dotted version `version` comes after `other`; an empty `other` comes before
+/// every version.
+fn is_newer_version(version: &str, other: &str) -> bool {
+    let parts = |version: &str| -> Vec<u64> {
+        version.split('.').map(|part| part.parse().unwrap_or(0)).collect()
+    };
+    parts(version) > parts(other)
+}
+
+/// Delay before a (re)started poller's first fetch. A burst of config changes replaces the
+/// subscription several times; only the last one survives long enough to spawn a fetch.
+const POLL_SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+/// Polls a search query every `interval` seconds, mapping each result into a message.
+///
+/// The subscription ID includes every value the poll depends on, so it restarts
+/// automatically when any of them changes. Callers build the request from the persisted
+/// config only (never from in-progress text inputs), so typing doesn't restart it.
+/// Bumping `generation` restarts it even when nothing else changed.
+fn poll_query(
+    request: SearchRequest,
+    interval: u64,
+    generation: u64,
+    on_result: impl Fn(Result<SearchResults, FetchError>) -> Message + Send + 'static,
+) -> Subscription<Message> {
+    Subscription::run_with_id(
+        (request.clone(), interval, generation),
+        cosmic::iced::stream::channel(4, move |mut channel| async move {
+            tokio::time::sleep(POLL_SETTLE_DELAY).await;
+            loop {
+                let result = github::fetch_pr_count(&request).await;
+                // Back off further when GitHub asks us to, never polling sooner than usual.
+                let backoff = result.as_ref().err().and_then(FetchError::backoff);
+                let _ = channel.send(on_result(result)).await;
+                let delay = Duration::from_secs(interval);
+                // Waking from suspend cuts the wait short, backoff included, so the badge
+                // is refreshed right away.
+                sleep_unless_resumed(backoff.map_or(delay, |backoff| backoff.max(delay))).await;
+            }
+        }),
+    )
+}
+
+/// Waits for `wait`, or less if the machine resumes from suspend meanwhile. Monotonic
+/// timers stop during suspend, so a resume shows up as a wall-clock jump between checks.
+async fn sleep_unless_resumed(wait: Duration) {
+    let deadline = tokio::time::Instant::now() + wait;
+    loop {
+        let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+        if remaining.is_zero() {
+            return;
+        }
+        let step = remaining.min(WAKE_CHECK_INTERVAL);
+        let before = SystemTime::now();
+        tokio::time::sleep(step).await;
+        let slept = SystemTime::now().duration_since(before).unwrap_or_default();
+        if slept > step + WAKE_CHECK_INTERVAL {
+            return;
+        }
+    }
 }
 
 /// The application model stores app-specific state used to describe its interface and
@@ -119,22 +868,118 @@ pub struct AppModel {
     core: cosmic::Core,
     /// The popup id.
     popup: Option<Id>,
+    /// Whether the popup stays open when clicking outside of it.
+    popup_pinned: bool,
     /// Configuration data that persists between application runs.
     config: Config,
     /// Handle used for writing config changes.
     config_handler: Option<cosmic_config::Config>,
+    /// Settings come from the administrator's managed config and can't be changed.
+    managed: bool,
+    /// Runtime state that persists between application runs.
+    state: State,
+    /// Handle used for writing state changes.
+    state_handler: Option<cosmic_config::Config>,
     /// Number of PRs waiting for review, or None if not yet fetched.
-    pr_count: Option<u32>,
+    pr_count: Option<u64>,
+    /// Whether `pr_count` came from the persisted cache rather than a live fetch.
+    count_is_cached: bool,
+    /// First page of PRs waiting for review, from the last successful fetch.
+    pull_requests: Vec<PullRequest>,
+    /// Review decisions and CI status of the listed PRs, by node ID.
+    pr_statuses: HashMap<String, PrStatus>,
+    /// Why the last lookup of `pr_statuses` failed, if it did.
+    pr_statuses_error: Option<FetchError>,
+    /// Result of the optional changes-requested query (None = not yet fetched or disabled).
+    changes_requested: Option<Result<u64, FetchError>>,
+    /// My open PRs with merge conflicts, when tracked.
+    conflicting: Option<Result<u64, FetchError>>,
+    /// Items of the configured project assigned to me, when one is set.
+    project_count: Option<Result<u64, FetchError>>,
+    /// Latest results of the popup tabs, by search query.
+    tab_results: HashMap<String, Result<SearchResults, FetchError>>,
+    /// Tab bar of the popup; each entry's data is its index, 0 being the main search.
+    tab_model: segmented_button::SingleSelectModel,
+    tab_name_input: String,
+    tab_query_input: String,
+    /// Latest results of the extra panel metrics, by search query.
+    extra_counts: HashMap<String, Result<u64, FetchError>>,
+    /// Count of the comparison search, when one is set.
+    comparison_count: Option<Result<u64, FetchError>>,
     /// Whether the last fetch resulted in an error.
-    fetch_error: Option<String>,
+    fetch_error: Option<FetchError>,
+    /// Failed fetches since the last successful one.
+    consecutive_failures: u32,
+    /// GitHub flagged the last search as incomplete, so the count may be too low.
+    count_incomplete: bool,
+    /// Part of the pollers' IDs; bumped to restart them with a fresh fetch.
+    poll_generation: u64,
+    realtime_url_input: String,
+    label_format_input: String,
+    outputs_input: String,
+    /// Feedback on the last action, shown at the bottom of the popup, and when it appeared.
+    transient_message: Option<(String, Instant)>,
+    /// When the applet started, for the quiet start period.
+    started_at: Instant,
+    /// Wall-clock time of the last completed main fetch, used to notice suspend/resume.
+    last_poll_at: Option<SystemTime>,
+    /// When the review queue was last opened because the count crossed the threshold.
+    auto_opened_at: Option<Instant>,
+    /// COSMIC's Do Not Disturb is on: no notifications or automatic browser tabs.
+    do_not_disturb: bool,
+    /// The desktop has animations turned off, so the badge doesn't pulse.
+    reduce_motion: bool,
+    /// When the count last changed, while the badge is still pulsing.
+    count_pulse_at: Option<Instant>,
+    /// When the shown count was last fetched successfully.
+    last_success_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Request stats of the most recent main fetch, successful or not.
+    last_fetch_stats: Option<FetchStats>,
+    /// Durations of the most recent main fetches, newest last.
+    fetch_durations: VecDeque<Duration>,
+    /// Recent counts with the time they were fetched, newest last.
+    count_history: VecDeque<(chrono::DateTime<chrono::Local>, u64)>,
+    /// Repository the PR list is narrowed to for this session, in `owner/name` form.
+    repo_filter: Option<String>,
+    /// IDs of the PRs that had been seen when the popup was opened; the rest are new.
+    seen_at_open: BTreeSet<u64>,
+    /// Whether the raw details of `fetch_error` are expanded in the popup.
+    show_error_details: bool,
     /// Whether the settings page is currently shown.
     show_settings: bool,
+    /// Index into `SETTINGS_SECTION_LABELS` of the section last picked from the list.
+    settings_section: Option<usize>,
     /// Temporary state for the PAT text input field.
     pat_input: String,
+    /// Temporary state for the terminal command text input field.
+    terminal_input: String,
+    token_command_input: String,
+    /// Temporary state for the search query text input field.
+    query_input: String,
+    comparison_input: String,
+    project_input: String,
+    project_status_input: String,
+    org_input: String,
+    /// The organization entered last wasn't a plausible name and wasn't saved.
+    org_input_invalid: bool,
+    /// Temporary state for the API version text input field.
+    api_version_input: String,
+    /// Custom badge colours being edited, by tier, until they are saved.
+    badge_color_inputs: HashMap<BadgeTier, String>,
+    /// Temporary state for the CA certificate path text input field.
+    ca_path_input: String,
     /// Result of gh auth status check (None = not yet checked).
     gh_status: Option<Result<String, String>>,
     /// Incremented to trigger a fresh gh auth status check.
     gh_check_id: u64,
+    /// Request description and outcome of the last test fetch run from settings.
+    test_fetch: Option<(String, Result<SearchResults, FetchError>)>,
+    /// Whether a test fetch is in flight.
+    test_fetch_running: bool,
+    /// Whether the reset-to-defaults confirmation is shown.
+    confirm_reset: bool,
+    /// Whether resetting to defaults keeps the saved PAT.
+    reset_keep_pat: bool,
 }
 
 impl Default for AppModel {
@@ -142,14 +987,67 @@ impl Default for AppModel {
         Self {
             core: cosmic::Core::default(),
             popup: None,
+            popup_pinned: false,
             config: Config::default(),
             config_handler: None,
+            managed: false,
+            state: State::default(),
+            state_handler: None,
             pr_count: None,
+            count_is_cached: false,
+            pull_requests: Vec::new(),
+            pr_statuses: HashMap::new(),
+            pr_statuses_error: None,
+            changes_requested: None,
+            conflicting: None,
+            project_count: None,
+            tab_results: HashMap::new(),
+            tab_model: segmented_button::SingleSelectModel::default(),
+            tab_name_input: String::new(),
+            tab_query_input: String::new(),
+            extra_counts: HashMap::new(),
+            comparison_count: None,
             fetch_error: None,
+            consecutive_failures: 0,
+            count_incomplete: false,
+            poll_generation: 0,
+            realtime_url_input: String::new(),
+            label_format_input: String::new(),
+            outputs_input: String::new(),
+            transient_message: None,
+            started_at: Instant::now(),
+            last_poll_at: None,
+            auto_opened_at: None,
+            do_not_disturb: false,
+            reduce_motion: false,
+            count_pulse_at: None,
+            last_success_at: None,
+            last_fetch_stats: None,
+            fetch_durations: VecDeque::new(),
+            count_history: VecDeque::new(),
+            repo_filter: None,
+            seen_at_open: BTreeSet::new(),
+            show_error_details: false,
             show_settings: false,
+            settings_section: None,
             pat_input: String::new(),
+            terminal_input: String::new(),
+            token_command_input: String::new(),
+            query_input: String::new(),
+            comparison_input: String::new(),
+            project_input: String::new(),
+            project_status_input: String::new(),
+            org_input: String::new(),
+            org_input_invalid: false,
+            api_version_input: String::new(),
+            badge_color_inputs: HashMap::new(),
+            ca_path_input: String::new(),
             gh_status: None,
             gh_check_id: 0,
+            test_fetch: None,
+            test_fetch_running: false,
+            confirm_reset: false,
+            reset_keep_pat: true,
         }
     }
 }
@@ -158,18 +1056,127 @@ impl Default for AppModel {
 #[derive(Debug, Clone)]
 pub enum Message {
     TogglePopup,
+    ScheduleTick,
+    TogglePinned,
+    MarkPrsSeen(Option<Id>),
     PopupClosed(Id),
     UpdateConfig(Config),
-    PRCountFetched(Result<u32, String>),
+    PRCountFetched(Result<SearchResults, FetchError>),
+    ChangesRequestedFetched(Result<u64, FetchError>),
+    ConflictsFetched(Result<u64, FetchError>),
+    ProjectFetched(Result<u64, FetchError>),
+    SetProjectInput(String),
+    SetProjectStatusInput(String),
+    SaveProject,
+    PrStatusesFetched(Result<HashMap<String, PrStatus>, FetchError>),
+    ExtraMetricFetched(String, Result<u64, FetchError>),
+    ComparisonFetched(Result<u64, FetchError>),
+    SetComparisonInput(String),
+    SaveComparisonQuery,
+    OpenSearch(String),
     OpenGitHub,
+    OpenUrl(String),
+    OpenOldest,
+    ToggleErrorDetails,
+    SetRepoFilter(Option<String>),
+    OpenTerminal,
     // Settings
     OpenSettings,
+    SelectSettingsSection(usize),
+    ScrollSettingsTo(f32),
+    DismissWhatsNew,
     CloseSettings,
     SetAuthMethod(AuthMethod),
+    SetAccountEnabled(bool),
+    SetUseGhToken(bool),
     SetPatInput(String),
     SavePat,
+    SetTokenCommandInput(String),
+    SaveTokenCommand,
+    SetTerminalInput(String),
+    SaveTerminalCommand,
+    SetQueryPreset(usize),
+    SetExtraMetric(usize, usize),
+    SetBadgeAggregate(bool),
+    TabFetched(String, Result<SearchResults, FetchError>),
+    SelectTab(segmented_button::Entity),
+    SetTabNameInput(String),
+    SetTabQueryInput(String),
+    AddTab,
+    RemoveTab(usize),
+    SetBadgeSumsTabs(bool),
+    SetQueryInput(String),
+    SaveQuery,
+    SetOrgInput(String),
+    SaveOrg,
+    SetApiVersionInput(String),
+    SaveApiVersion,
+    SetAllowInsecureTls(bool),
+    SetHttp1Only(bool),
+    SetNoConnectionReuse(bool),
+    SetCaPathInput(String),
+    SetRealtimeUrlInput(String),
+    SaveRealtimeUrl,
+    RealtimeEvent,
+    SetLabelFormatInput(String),
+    SetOutputsInput(String),
+    SaveOutputs,
+    SaveLabelFormat,
+    SaveCaPath,
     SetPollInterval(usize),
+    SetForegroundInterval(usize),
+    SetTrackChangesRequested(bool),
+    SetTrackConflicts(bool),
+    ResetPeak,
+    SetExcludeOwn(bool),
+    SetIncludeApproved(bool),
+    SetAccurateCount(bool),
+    SetShowDailyProgress(bool),
+    SetShowCountHistory(bool),
+    SetTimeDisplay(usize),
+    SetTerminalGhCommand(usize),
+    SetCountClick(usize),
+    SetMinAge(usize),
+    SetPrSort(usize),
+    SetPrListLimit(usize),
+    SetRepoVisibility(usize),
+    SetBadgePalette(usize),
+    SetBadgeAccent(bool),
+    SetBadgeErrors(bool),
+    SetShowIcon(bool),
+    SetAutohide(bool),
+    SetRowField(RowField, bool),
+    SetDimAfterFailures(usize),
+    SetDimDuringDnd(bool),
+    SetStaleAfter(usize),
+    DoNotDisturbChanged(bool),
+    SetAutoOpenThreshold(usize),
+    SetBadgeErrorDot(bool),
+    SetBadgeColorInput(BadgeTier, String),
+    SaveBadgeColor(BadgeTier),
+    SetNotifications(bool),
+    SetQuietStart(usize),
+    SetWorkingHoursEnabled(bool),
+    SetWorkStart(usize),
+    SetWorkEnd(usize),
+    ToggleWorkDay(chrono::Weekday, bool),
     CheckGhStatus,
+    CopyDiagnostics,
+    CopyCurlCommand,
+    ClearCache,
+    ExpireToast,
+    PulseTick,
+    AnimationsChecked(bool),
+    SetAnimateCount(bool),
+    OpenConfigDir,
+    ReloadConfig,
+    ConfigOpened(Option<cosmic_config::Config>),
+    RunTestFetch,
+    TestFetchFinished(String, Result<SearchResults, FetchError>),
+    RequestReset,
+    CancelReset,
+    SetResetKeepPat(bool),
+    ConfirmReset,
     GhStatusFetched(Result<String, String>),
 }
 
@@ -200,31 +1207,90 @@ impl cosmic::Application for AppModel {
         core: cosmic::Core,
         _flags: Self::Flags,
     ) -> (Self, Task<cosmic::Action<Self::Message>>) {
-        let config_handler = cosmic_config::Config::new(Self::APP_ID, Config::VERSION).ok();
-        let mut config = config_handler
-            .as_ref()
-            .map(|h| match Config::get_entry(h) {
-                Ok(config) => config,
-                Err((_errors, config)) => config,
+        // An administrator-managed config replaces the user's; without a handler nothing
+        // is ever written back.
+        let managed_config = Config::load_managed();
+        let managed = managed_config.is_some();
+        // Nothing saved yet means a fresh install, which has no update to tell about.
+        let fresh_install = Config::dir(Self::APP_ID)
+            .and_then(|dir| std::fs::read_dir(dir).ok())
+            .is_none_or(|mut entries| entries.next().is_none());
+        let (config_handler, mut config) = match managed_config {
+            Some(config) => (None, config),
+            None => Config::load(Self::APP_ID),
+        };
+        // Keep trying in the background rather than holding up the panel.
+        let reopen_config = if managed || config_handler.is_some() {
+            Task::none()
+        } else {
+            Task::perform(Config::reopen(Self::APP_ID.to_string()), |handler| {
+                cosmic::Action::App(Message::ConfigOpened(handler))
             })
-            .unwrap_or_default();
-
-        // Migrate: old configs may have poll_interval_secs = 0 (u64 default).
-        if config.poll_interval_secs == 0 {
-            config.poll_interval_secs = 60;
+        };
+        if fresh_install && config.last_seen_version.is_empty() {
+            config.last_seen_version = APP_VERSION.to_string();
+            if let Some(handler) = &config_handler {
+                let _ = config.write_entry(handler);
+            }
         }
+        let (state_handler, state) = State::load(Self::APP_ID);
 
         let pat_input = config.github_pat.clone();
+        let terminal_input = config.terminal_command.clone();
+        let token_command_input = config.token_command.clone();
+        let query_input = config.search_query.clone();
+        let comparison_input = config.comparison_query.clone();
+        let project_input = config.project.clone();
+        let project_status_input = config.project_ignored_status.clone();
+        let org_input = config.org_filter.clone().unwrap_or_default();
+        let realtime_url_input = config.realtime_url.clone();
+        let label_format_input = config.label_format.clone();
+        let outputs_input = config.outputs.join(", ");
+        let api_version_input = config.api_version.clone();
+        let count_history = cache::read(COUNT_HISTORY_CACHE_KEY)
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .unwrap_or_default();
+        let ca_path_input = ca_path_text(&config);
+
+        // Show the last known count until the first live fetch completes.
+        let pr_count = state.cached_pr_count.filter(|_| config.account_enabled);
 
-        let app = AppModel {
+        let mut app = AppModel {
             core,
             config,
             config_handler,
+            managed,
+            state,
+            state_handler,
+            pr_count,
+            count_is_cached: pr_count.is_some(),
             pat_input,
+            terminal_input,
+            token_command_input,
+            query_input,
+            comparison_input,
+            project_input,
+            project_status_input,
+            org_input,
+            realtime_url_input,
+            label_format_input,
+            outputs_input,
+            api_version_input,
+            ca_path_input,
+            count_history,
+            do_not_disturb: NotificationsConfig::load().do_not_disturb,
             ..Default::default()
         };
 
-        (app, Task::none())
+        app.rebuild_tab_model();
+
+        // Units started with Type=notify can be ordered after the applet from here on.
+        systemd::notify_ready();
+
+        let check_animations = Task::perform(animations_enabled(), |enabled| {
+            cosmic::Action::App(Message::AnimationsChecked(enabled))
+        });
+        (app, Task::batch([check_animations, reopen_config]))
     }
 
     fn on_close_requested(&self, id: Id) -> Option<Message> {
@@ -235,118 +1301,406 @@ impl cosmic::Application for AppModel {
     fn view(&self) -> Element<'_, Self::Message> {
         use cosmic::iced::{
             alignment::{Horizontal, Vertical},
-            Background, Border, Color, Length,
+            Length,
         };
 
+        // Nothing to do and nothing wrong: take no panel space until something comes in.
+        if self.config.autohide && self.fully_idle() {
+            return widget::Space::new(0, 0).into();
+        }
+        // Not one of the chosen outputs: this instance stays out of the way.
+        if !self.on_chosen_output() {
+            return widget::Space::new(0, 0).into();
+        }
+
         let icon_size = self.core.applet.suggested_size(true).0;
 
         // Wrap icon with padding: top/left=2 for breathing room, right/bottom=5
         // so the Stack has extra space for the badge to extend beyond the icon edge.
-        let icon: Element<_> = widget::container(
-            widget::icon::from_name("com.laeborg.CosmicAppletGithubStatus").size(icon_size),
-        )
-        .padding([2, 5, 5, 2])
-        .into();
+        // A sustained run of failures greys the icon out; a single failed fetch doesn't.
+        // Do Not Disturb can grey it out as well.
+        let dimmed = (self.config.dim_after_failures > 0
+            && self.consecutive_failures >= self.config.dim_after_failures)
+            || (self.config.dim_during_dnd && self.do_not_disturb);
+        let mut icon = widget::icon(panel_icon()).size(icon_size);
+        if dimmed {
+            icon = icon.class(cosmic::theme::Svg::Custom(Rc::new(|theme| {
+                let mut color: Color = theme.cosmic().on_bg_color().into();
+                color.a = 0.4;
+                cosmic::iced::widget::svg::Style { color: Some(color) }
+            })));
+        }
+        let icon: Element<_> = widget::container(icon).padding([2, 5, 5, 2]).into();
+
+        let theme = cosmic::theme::active();
+        let high_contrast = self.uses_high_contrast();
 
         // Badge: colored circle with label. Color depends on severity.
-        let badge_info: Option<(String, Color)> = match (&self.fetch_error, self.pr_count) {
-            (Some(_), _) => Some(("!".into(), Color::from_rgb(0.82, 0.18, 0.18))),
-            (_, Some(0)) => Some(("0".into(), Color::from_rgb(0.13, 0.65, 0.30))),
-            (_, Some(n)) if n <= 5 => Some((n.to_string(), Color::from_rgb(0.15, 0.45, 0.85))),
-            (_, Some(n)) if n <= 10 => Some((n.to_string(), Color::from_rgb(0.80, 0.65, 0.10))),
-            (_, Some(n)) => Some((n.to_string(), Color::from_rgb(0.82, 0.18, 0.18))),
-            (_, None) => None,
-        };
+        let error_dot = shows_error_dot(self.fetch_error.as_ref(), self.pr_count, &self.config);
+        let badge_info = compute_badge(
+            self.fetch_error.as_ref(),
+            self.badge_count(),
+            self.count_is_stale(),
+            &self.config,
+        );
 
-        let content: Element<_> = if let Some((label, bg_color)) = badge_info {
-            let badge: Element<_> = widget::container(
-                widget::text(label).size(9).class(Color::WHITE),
-            )
-            .width(13)
-            .height(13)
-            .align_x(Horizontal::Center)
-            .align_y(Vertical::Center)
-            .class(cosmic::theme::Container::Custom(Box::new(move |_| {
-                cosmic::iced_widget::container::Style {
-                    background: Some(Background::Color(bg_color)),
-                    border: Border {
-                        radius: 100.0.into(),
-                        ..Default::default()
-                    },
-                    ..Default::default()
+        // A custom colour wins; otherwise only the normal tier follows the accent colour
+        // and the others keep their meaning. A cached count is muted until a live fetch
+        // confirms it.
+        let tier_color = |tier: BadgeTier| -> Color {
+            if let Some(custom) = parse_hex_color(tier.custom_color(&self.config)) {
+                custom
+            } else if tier == BadgeTier::Normal && self.config.badge_accent {
+                theme.cosmic().accent_color().into()
+            } else {
+                tier.color(high_contrast)
+            }
+        };
+        let badge_info = badge_info.map(|(label, tier)| {
+            let label = if !self.config.show_icon && tier != BadgeTier::Error {
+                format_label(&self.config.label_format, &label)
+            } else {
+                label
+            };
+            let color = tier_color(tier);
+            let color = if self.count_is_cached && tier != BadgeTier::Error {
+                Color { a: 0.5, ..color }
+            } else {
+                color
+            };
+            // After a count change the badge flashes towards white and fades back.
+            let color = match self.count_pulse_at {
+                Some(started) if tier != BadgeTier::Error => {
+                    let progress = started.elapsed().as_secs_f32() / COUNT_PULSE.as_secs_f32();
+                    let glow = 0.6 * (1.0 - progress.min(1.0));
+                    Color {
+                        r: color.r + (1.0 - color.r) * glow,
+                        g: color.g + (1.0 - color.g) * glow,
+                        b: color.b + (1.0 - color.b) * glow,
+                        a: color.a,
+                    }
                 }
-            })))
-            .into();
+                _ => color,
+            };
+            (label, color)
+        });
 
-            cosmic::iced::widget::Stack::new()
-                .push(icon)
-                .push(
+        let main_button: Element<_> = if self.config.show_icon {
+            let content: Element<_> = if let Some((label, bg_color)) = badge_info {
+                let badge: Element<_> = widget::container(
+                    widget::text(label).size(9).class(Color::WHITE),
+                )
+                .width(13)
+                .height(13)
+                .align_x(Horizontal::Center)
+                .align_y(Vertical::Center)
+                .class(badge_style(bg_color))
+                .into();
+
+                let mut stack = cosmic::iced::widget::Stack::new().push(icon).push(
                     widget::container(badge)
                         .width(Length::Fill)
                         .height(Length::Fill)
                         .align_x(Horizontal::Right)
                         .align_y(Vertical::Bottom),
-                )
+                );
+
+                if error_dot {
+                    let dot = widget::container(widget::Space::new(0, 0))
+                        .width(6)
+                        .height(6)
+                        .class(badge_style(tier_color(BadgeTier::Error)));
+                    stack = stack.push(
+                        widget::container(dot)
+                            .width(Length::Fill)
+                            .height(Length::Fill)
+                            .align_x(Horizontal::Right)
+                            .align_y(Vertical::Top),
+                    );
+                }
+
+                stack.into()
+            } else {
+                icon
+            };
+
+            self.core
+                .applet
+                .button_from_element(content, true)
+                .on_press(Message::TogglePopup)
                 .into()
         } else {
-            icon
-        };
+            // Compact mode: a larger pill with the count and nothing else.
+            let content: Element<_> = match badge_info {
+                Some((label, bg_color)) => count_pill(label, bg_color),
+                None => widget::text("…").size(12).into(),
+            };
 
-        self.core
-            .applet
-            .button_from_element(content, true)
-            .on_press(Message::TogglePopup)
+            let button = self
+                .core
+                .applet
+                .button_from_element(content, false)
+                .on_press(Message::TogglePopup);
+            widget::tooltip(
+                button,
+                widget::text(fl!("pr-count-label")),
+                widget::tooltip::Position::Bottom,
+            )
             .into()
+        };
+
+        if self.config.extra_metrics.is_empty() || self.config.badge_aggregate {
+            return main_button;
+        }
+
+        // Extra metrics sit next to the main button as pills of their own, each opening
+        // its search on GitHub.
+        let extras = self
+            .config
+            .extra_metrics
+            .iter()
+            .take(MAX_EXTRA_METRICS)
+            .map(|query| {
+                let (label, color) = match self.extra_counts.get(query) {
+                    Some(Ok(count)) => {
+                        (badge_label(*count), tier_color(BadgeTier::for_count(*count)))
+                    }
+                    Some(Err(_)) => ("!".to_string(), tier_color(BadgeTier::Error)),
+                    None => ("…".to_string(), Color::from_rgb(0.5, 0.5, 0.5)),
+                };
+                self.core
+                    .applet
+                    .button_from_element(count_pill(label, color), false)
+                    .on_press(Message::OpenSearch(query.clone()))
+                    .into()
+            });
+
+        if self.core.applet.is_horizontal() {
+            widget::row()
+                .push(main_button)
+                .extend(extras)
+                .align_y(Alignment::Center)
+                .into()
+        } else {
+            widget::column()
+                .push(main_button)
+                .extend(extras)
+                .align_x(Alignment::Center)
+                .into()
+        }
     }
 
-    /// Popup window: dispatches to main view or settings view.
+    /// Popup window: dispatches to main view or settings view, below the pin controls.
     fn view_window(&self, _id: Id) -> Element<'_, Self::Message> {
-        let content: Element<_> = if self.show_settings {
+        // The settings page pins its own header; the main page scrolls below the pin bar.
+        let page: Element<_> = if self.show_settings {
             self.settings_view()
         } else {
-            self.main_view()
+            scrolled(self.main_view())
         };
 
+        let mut pin_bar = widget::row()
+            .push(widget::horizontal_space())
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .padding([4, 12, 0, 12]);
+        if self.popup_pinned {
+            pin_bar = pin_bar
+                .push(widget::text::caption(fl!("pinned")))
+                .push(widget::button::text(fl!("unpin")).on_press(Message::TogglePinned))
+                .push(widget::button::text(fl!("close")).on_press(Message::TogglePopup));
+        } else {
+            pin_bar =
+                pin_bar.push(widget::button::text(fl!("pin")).on_press(Message::TogglePinned));
+        }
+
+        let mut content = widget::column().push(pin_bar).push(page);
+        if let Some((message, _)) = &self.transient_message {
+            content = content.push(
+                widget::container(widget::text::caption(message.clone()))
+                    .padding([4, 12, 8, 12])
+                    .width(cosmic::iced::Length::Fill)
+                    .align_x(cosmic::iced::alignment::Horizontal::Center),
+            );
+        }
         self.core.applet.popup_container(content).into()
     }
 
     /// Background subscriptions.
     fn subscription(&self) -> Subscription<Self::Message> {
-        let auth_method = self.config.auth_method.clone();
-        let pat = self.config.github_pat.clone();
+        // The interval is part of the pollers' IDs, so opening or closing the popup
+        // restarts them at the new pace.
+        let interval = if self.popup.is_some() && self.config.foreground_interval_secs > 0 {
+            self.config.foreground_interval_secs
+        } else {
+            self.config.poll_interval_secs
+        };
+
+        let mut subs = Vec::new();
+        if !self.managed {
+            subs.push(
+                self.core()
+                    .watch_config::<Config>(Self::APP_ID)
+                    .map(|update| Message::UpdateConfig(update.config)),
+            );
+        }
+        subs.push(
+            self.core()
+                .watch_config::<NotificationsConfig>(NotificationsConfig::ID)
+                .map(|update| Message::DoNotDisturbChanged(update.config.do_not_disturb)),
+        );
+
+        // Checks whether the toast has been up long enough; only runs while one is shown.
+        if self.transient_message.is_some() {
+            subs.push(
+                cosmic::iced::time::every(Duration::from_secs(1)).map(|_| Message::ExpireToast),
+            );
+        }
+
+        // Redraws the badge while it pulses after a count change; stops with the pulse.
+        if self.count_pulse_at.is_some() {
+            subs.push(
+                cosmic::iced::time::every(Duration::from_millis(30)).map(|_| Message::PulseTick),
+            );
+        }
+
+        // Main PR poller, which drives the panel badge.
+        // Outside working hours nothing is fetched; the last known count stays on the panel.
+        // Instances on other outputs show nothing, so they don't fetch or notify either.
+        let polling =
+            self.config.account_enabled && self.is_working_time() && self.on_chosen_output();
+        if self.config.working_hours_enabled {
+            // Re-evaluates the schedule, starting or stopping the pollers at its edges.
+            subs.push(
+                cosmic::iced::time::every(Duration::from_secs(60)).map(|_| Message::ScheduleTick),
+            );
+        }
+
+        if polling {
+            subs.push(poll_query(
+                review_search(&self.config),
+                interval,
+                self.poll_generation,
+                Message::PRCountFetched,
+            ));
+        }
 
-        let interval = self.config.poll_interval_secs;
+        if polling && self.config.track_changes_requested {
+            subs.push(poll_query(
+                count_search(&self.config, CHANGES_REQUESTED_QUERY),
+                interval,
+                self.poll_generation,
+                |result| Message::ChangesRequestedFetched(result.map(|r| r.total_count)),
+            ));
+        }
 
-        let mut subs = vec![
-            // Main PR poller — subscription ID includes all relevant config values,
-            // so it restarts automatically when any of them changes.
-            Subscription::run_with_id(
-                (auth_method.clone(), pat.clone(), interval),
+        // Projects aren't searchable either; the request only carries the credentials.
+        if polling && !self.config.project.is_empty() {
+            let request = count_search(&self.config, "");
+            let project = self.config.project.clone();
+            let ignored_status = self.config.project_ignored_status.clone();
+            subs.push(Subscription::run_with_id(
+                (
+                    "project",
+                    request.clone(),
+                    project.clone(),
+                    ignored_status.clone(),
+                    interval,
+                    self.poll_generation,
+                ),
                 cosmic::iced::stream::channel(4, move |mut channel| async move {
+                    tokio::time::sleep(POLL_SETTLE_DELAY).await;
                     loop {
-                        let result = fetch_pr_count(auth_method.clone(), pat.clone()).await;
-                        let _ = channel.send(Message::PRCountFetched(result)).await;
-                        tokio::time::sleep(Duration::from_secs(interval)).await;
+                        let result =
+                            github::fetch_project_count(&request, &project, &ignored_status)
+                                .await;
+                        let _ = channel.send(Message::ProjectFetched(result)).await;
+                        sleep_unless_resumed(Duration::from_secs(interval)).await;
                     }
                 }),
-            ),
-            self.core()
-                .watch_config::<Config>(Self::APP_ID)
-                .map(|update| Message::UpdateConfig(update.config)),
-        ];
+            ));
+        }
 
-        // GH auth status checker — only active when settings is open and GhCli is selected.
-        // gh_check_id changes whenever a fresh check is requested, forcing a new subscription.
-        if self.show_settings && matches!(self.config.auth_method, AuthMethod::GhCli) {
+        // Mergeability is only available through GraphQL, so this has a poller of its own.
+        if polling && self.config.track_conflicts {
+            let request = count_search(&self.config, AUTHORED_QUERY);
+            subs.push(Subscription::run_with_id(
+                ("conflicts", request.clone(), interval, self.poll_generation),
+                cosmic::iced::stream::channel(4, move |mut channel| async move {
+                    tokio::time::sleep(POLL_SETTLE_DELAY).await;
+                    loop {
+                        let result = github::fetch_conflicting_count(&request).await;
+                        let _ = channel.send(Message::ConflictsFetched(result)).await;
+                        sleep_unless_resumed(Duration::from_secs(interval)).await;
+                    }
+                }),
+            ));
+        }
+
+        // Extra panel metrics. Identical queries share a poller, as the IDs match.
+        if polling {
+            for query in self.config.extra_metrics.iter().take(MAX_EXTRA_METRICS) {
+                let query = query.clone();
+                subs.push(poll_query(
+                    count_search(&self.config, &query),
+                    interval,
+                    self.poll_generation,
+                    move |result| {
+                        Message::ExtraMetricFetched(query.clone(), result.map(|r| r.total_count))
+                    },
+                ));
+            }
+            // The date in the query changes at midnight, which restarts the poller.
+            if let Some(query) = comparison_query(&self.config) {
+                subs.push(poll_query(
+                    count_search(&self.config, &query),
+                    interval,
+                    self.poll_generation,
+                    |result| Message::ComparisonFetched(result.map(|r| r.total_count)),
+                ));
+            }
+            // Relay events only restart the pollers for an immediate fetch; polling goes on
+            // regardless, so a dropped relay connection just means waiting for the next poll.
+            if !self.config.realtime_url.is_empty() {
+                let url = self.config.realtime_url.clone();
+                subs.push(Subscription::run_with_id(
+                    ("realtime", url.clone()),
+                    cosmic::iced::stream::channel(4, move |mut channel| async move {
+                        loop {
+                            let result = realtime::listen(&url, || {
+                                let _ = channel.try_send(Message::RealtimeEvent);
+                            })
+                            .await;
+                            if let Err(why) = result {
+                                eprintln!("realtime relay {url}: {why}");
+                            }
+                            tokio::time::sleep(REALTIME_RECONNECT_DELAY).await;
+                        }
+                    }),
+                ));
+            }
+            for tab in &self.config.tabs {
+                let query = tab.query.clone();
+                subs.push(poll_query(
+                    list_search(&self.config, filtered_query(&tab.query, &self.config)),
+                    interval,
+                    self.poll_generation,
+                    move |result| Message::TabFetched(query.clone(), result),
+                ));
+            }
+        }
+
+        // GH auth status checker — only active when settings is open and GhCli is selected.
+        // gh_check_id changes whenever a fresh check is requested, forcing a new subscription.
+        // The stream ends after its one result; a finished subscription isn't restarted while
+        // its ID stays the same, and closing settings drops a check that is still running.
+        if self.show_settings && matches!(self.config.auth_method, AuthMethod::GhCli) {
             let check_id = self.gh_check_id;
             subs.push(Subscription::run_with_id(
                 check_id,
                 cosmic::iced::stream::channel(1, |mut channel| async move {
-                    let result = check_gh_status().await;
+                    let result = github::check_gh_status().await;
                     let _ = channel.send(Message::GhStatusFetched(result)).await;
-                    // Hang after sending — subscription is dropped when settings closes
-                    // or when gh_check_id changes.
-                    futures_util::future::pending::<()>().await;
                 }),
             ));
         }
@@ -357,87 +1711,884 @@ impl cosmic::Application for AppModel {
     /// Handles messages emitted by the application and its widgets.
     fn update(&mut self, message: Self::Message) -> Task<cosmic::Action<Self::Message>> {
         match message {
-            Message::PRCountFetched(Ok(count)) => {
-                self.pr_count = Some(count);
+            Message::PRCountFetched(Ok(results)) => {
+                self.record_fetch_stats(results.stats);
+                self.notify_count_change(results.total_count);
+                self.auto_open(results.total_count);
+                if self.count_history.len() == COUNT_HISTORY_LEN {
+                    self.count_history.pop_front();
+                }
+                self.count_history
+                    .push_back((chrono::Local::now(), results.total_count));
+                if let Ok(json) = serde_json::to_vec(&self.count_history) {
+                    let _ = cache::write(COUNT_HISTORY_CACHE_KEY, &json);
+                }
+                let changed = self.pr_count.is_some_and(|count| count != results.total_count);
+                if changed && self.config.animate_count && !self.reduce_motion {
+                    self.count_pulse_at = Some(Instant::now());
+                }
+                self.pr_count = Some(results.total_count);
+                self.consecutive_failures = 0;
+                // An incomplete search is retried once, soon, instead of at the next poll.
+                let retry_incomplete = results.incomplete && !self.count_incomplete;
+                self.count_incomplete = results.incomplete;
+                self.last_success_at = Some(chrono::Utc::now());
+                self.count_is_cached = false;
+                self.pull_requests = results.items;
                 self.fetch_error = None;
+
+                // The daily peak resets at local midnight.
+                let today = chrono::Local::now().date_naive().to_string();
+                let daily_peak = if self.state.daily_peak_date == today {
+                    self.state.daily_peak.max(results.total_count)
+                } else {
+                    results.total_count
+                };
+
+                let all_time_peak = self.state.all_time_peak.max(results.total_count);
+                let pat_expires_at = results.stats.token_expires_at;
+
+                if self.state.cached_pr_count != self.pr_count
+                    || self.state.daily_peak != daily_peak
+                    || self.state.daily_peak_date != today
+                    || self.state.all_time_peak != all_time_peak
+                    || self.state.pat_expires_at != pat_expires_at
+                    || self.state.last_fetch_failed
+                {
+                    self.state.cached_pr_count = self.pr_count;
+                    self.state.daily_peak = daily_peak;
+                    self.state.all_time_peak = all_time_peak;
+                    self.state.pat_expires_at = pat_expires_at;
+                    self.state.daily_peak_date = today;
+                    self.state.last_fetch_failed = false;
+                    if let Some(handler) = &self.state_handler {
+                        let _ = self.state.write_entry(handler);
+                    }
+                }
+
+                // Review decisions and CI status aren't in the search results; look them up
+                // for the PRs that are actually listed, if the list shows either.
+                let listed = if self.config.row_show_review_state || self.config.row_show_ci {
+                    self.config.pr_list_limit
+                } else {
+                    0
+                };
+                let node_ids: Vec<String> = self
+                    .pull_requests
+                    .iter()
+                    .take(listed)
+                    .map(|pr| pr.node_id.clone())
+                    .collect();
+                let request = review_search(&self.config);
+                let states_request = request.clone();
+                let mut tasks = vec![Task::perform(
+                    async move { github::fetch_pr_statuses(&states_request, &node_ids).await },
+                    |result| cosmic::Action::App(Message::PrStatusesFetched(result)),
+                )];
+                if retry_incomplete {
+                    tasks.push(Task::perform(
+                        async move {
+                            tokio::time::sleep(INCOMPLETE_RETRY_DELAY).await;
+                            github::fetch_pr_count(&request).await
+                        },
+                        |result| cosmic::Action::App(Message::PRCountFetched(result)),
+                    ));
+                }
+                return Task::batch(tasks);
+            }
+            Message::PrStatusesFetched(result) => {
+                // On failure the list keeps the last markers and says why under the list.
+                match result {
+                    Ok(statuses) => {
+                        self.pr_statuses = statuses;
+                        self.pr_statuses_error = None;
+                    }
+                    Err(err) => {
+                        eprintln!("PR status lookup failed: {err}");
+                        self.pr_statuses_error = Some(err);
+                    }
+                }
             }
             Message::PRCountFetched(Err(err)) => {
+                self.last_poll_at = Some(SystemTime::now());
+                self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+                self.record_fetch_stats(err.stats);
                 self.fetch_error = Some(err);
+                if !self.state.last_fetch_failed {
+                    self.state.last_fetch_failed = true;
+                    if let Some(handler) = &self.state_handler {
+                        let _ = self.state.write_entry(handler);
+                    }
+                }
+            }
+            Message::ChangesRequestedFetched(result) => {
+                self.changes_requested = Some(result);
+            }
+            Message::ConflictsFetched(result) => {
+                self.conflicting = Some(result);
+            }
+            Message::ProjectFetched(result) => {
+                self.project_count = Some(result);
+            }
+            Message::SetProjectInput(input) => {
+                self.project_input = input;
+            }
+            Message::SetProjectStatusInput(input) => {
+                self.project_status_input = input;
+            }
+            Message::SaveProject => {
+                self.config.project = self.project_input.trim().to_string();
+                self.config.project_ignored_status = self.project_status_input.trim().to_string();
+                self.project_count = None;
+                if self.save_config() {
+                    self.show_toast(fl!("saved"));
+                }
+            }
+            Message::ComparisonFetched(result) => {
+                self.comparison_count = Some(result);
+            }
+            Message::SetComparisonInput(input) => {
+                self.comparison_input = input;
+            }
+            Message::SaveComparisonQuery => {
+                self.config.comparison_query = self.comparison_input.trim().to_string();
+                self.comparison_count = None;
+                if self.save_config() {
+                    self.show_toast(fl!("saved"));
+                }
+            }
+            Message::ExtraMetricFetched(query, result) => {
+                self.extra_counts.insert(query, result);
+            }
+            Message::OpenSearch(query) => {
+                let query = filtered_query(&query, &self.config);
+                self.open_external(github::web_search_url(github::API_BASE_URL, &query));
             }
             Message::OpenGitHub => {
-                let _ = std::process::Command::new("xdg-open")
-                    .arg(GITHUB_REVIEW_URL)
-                    .spawn();
+                // Open the exact search behind the badge, filters included.
+                let query = main_query(&self.config);
+                self.open_external(github::web_search_url(github::API_BASE_URL, &query));
+            }
+            Message::OpenUrl(url) => {
+                self.open_external(url);
+            }
+            Message::OpenOldest => {
+                if let Some(url) = self.oldest_pr().map(|pr| pr.html_url.clone()) {
+                    self.open_external(url);
+                }
+            }
+            Message::SetRepoFilter(repo) => {
+                self.repo_filter = repo;
+            }
+            Message::ToggleErrorDetails => {
+                self.show_error_details = !self.show_error_details;
+            }
+            Message::OpenTerminal => {
+                let args = terminal_gh_args(
+                    self.config.terminal_gh_command,
+                    &main_query(&self.config),
+                    self.repo_filter.as_deref(),
+                );
+                open_in_terminal(&self.config.terminal_command, &args);
             }
             Message::UpdateConfig(config) => {
-                // Don't overwrite PAT input while user is editing in settings
+                // Our own writes echo back unchanged; nothing to do for those.
+                if config == self.config {
+                    return Task::none();
+                }
+                // Don't overwrite text inputs while user is editing in settings
                 if !self.show_settings {
                     self.pat_input = config.github_pat.clone();
+                    self.terminal_input = config.terminal_command.clone();
+                    self.token_command_input = config.token_command.clone();
+                    self.query_input = config.search_query.clone();
+                    self.comparison_input = config.comparison_query.clone();
+                    self.project_input = config.project.clone();
+                    self.project_status_input = config.project_ignored_status.clone();
+                    self.org_input = config.org_filter.clone().unwrap_or_default();
+                    self.realtime_url_input = config.realtime_url.clone();
+                    self.label_format_input = config.label_format.clone();
+                    self.outputs_input = config.outputs.join(", ");
+                    self.api_version_input = config.api_version.clone();
+                    self.badge_color_inputs.clear();
+                    self.ca_path_input = ca_path_text(&config);
+                }
+                if !config.account_enabled {
+                    self.clear_counts();
+                } else {
+                    if !config.track_changes_requested {
+                        self.changes_requested = None;
+                    }
+                    if !config.track_conflicts {
+                        self.conflicting = None;
+                    }
+                    if config.comparison_query != self.config.comparison_query {
+                        self.comparison_count = None;
+                    }
+                    if config.project.is_empty() {
+                        self.project_count = None;
+                    }
                 }
+                self.extra_counts
+                    .retain(|query, _| config.extra_metrics.contains(query));
+                let tabs_changed = config.tabs != self.config.tabs;
                 self.config = config;
+                if tabs_changed {
+                    self.rebuild_tab_model();
+                }
             }
             Message::TogglePopup => {
                 return if let Some(p) = self.popup.take() {
                     self.show_settings = false;
+                    self.popup_pinned = false;
                     destroy_popup(p)
                 } else {
-                    let new_id = Id::unique();
-                    self.popup.replace(new_id);
-                    let mut popup_settings = self.core.applet.get_popup_settings(
-                        self.core.main_window_id().unwrap(),
-                        new_id,
-                        None,
-                        None,
-                        None,
-                    );
-                    popup_settings.positioner.size_limits = Limits::NONE
-                        .max_width(300.0)
-                        .min_width(220.0)
-                        .min_height(80.0)
-                        .max_height(500.0);
-                    get_popup(popup_settings)
+                    // Without usable credentials the main view has nothing to show, so
+                    // go straight to settings.
+                    if !self.has_usable_auth() {
+                        self.open_settings();
+                    }
+
+                    self.seen_at_open = self.state.seen_pr_ids.clone();
+                    let open = self.open_popup();
+                    // PRs count as seen once the popup has been open for a moment.
+                    let id = self.popup;
+                    let mark_seen = Task::perform(tokio::time::sleep(MARK_SEEN_DELAY), move |_| {
+                        cosmic::Action::App(Message::MarkPrsSeen(id))
+                    });
+                    Task::batch([open, mark_seen])
                 };
             }
+            Message::MarkPrsSeen(id) => {
+                if id.is_some() && self.popup == id {
+                    // Only PRs still pending need remembering.
+                    let seen: BTreeSet<u64> = self.pull_requests.iter().map(|pr| pr.id).collect();
+                    if self.state.seen_pr_ids != seen {
+                        self.state.seen_pr_ids = seen;
+                        if let Some(handler) = &self.state_handler {
+                            let _ = self.state.write_entry(handler);
+                        }
+                    }
+                }
+            }
+            Message::ScheduleTick => {
+                // Nothing to update; the subscriptions are re-evaluated after every message.
+            }
+            Message::TogglePinned => {
+                self.popup_pinned = !self.popup_pinned;
+                // Whether outside clicks dismiss the popup is fixed when it is created,
+                // so swap the open popup for one with the new grab behaviour.
+                return self.reopen_popup();
+            }
             Message::PopupClosed(id) => {
                 if self.popup.as_ref() == Some(&id) {
                     self.popup = None;
                     self.show_settings = false;
+                    self.popup_pinned = false;
                 }
             }
             Message::OpenSettings => {
-                self.show_settings = true;
-                self.gh_status = None;
-                self.gh_check_id += 1;
+                if self.managed {
+                    return Task::none();
+                }
+                self.open_settings();
+                // Give keyboard users a predictable starting point; Tab then follows the
+                // section list and then the sections from top to bottom.
+                return self
+                    .reopen_popup()
+                    .chain(widget::button::focus(SETTINGS_BACK_ID.clone()));
+            }
+            Message::SelectSettingsSection(idx) => {
+                if idx < SETTINGS_SECTION_LABELS.len() {
+                    self.settings_section = Some(idx);
+                    // Where the section starts is only known from the current layout.
+                    return cosmic::iced::advanced::widget::operate(SectionOffset::new(idx))
+                        .map(|offset| cosmic::Action::App(Message::ScrollSettingsTo(offset)));
+                }
+            }
+            Message::ScrollSettingsTo(offset) => {
+                return cosmic::iced::widget::scrollable::scroll_to(
+                    SETTINGS_SCROLL_ID.clone(),
+                    cosmic::iced::widget::scrollable::AbsoluteOffset { x: 0.0, y: offset },
+                );
+            }
+            Message::DismissWhatsNew => {
+                self.config.last_seen_version = APP_VERSION.to_string();
+                self.save_config();
             }
             Message::CloseSettings => {
                 self.show_settings = false;
+                return self.reopen_popup();
             }
             Message::SetAuthMethod(method) => {
                 self.config.auth_method = method;
                 self.gh_status = None;
                 self.gh_check_id += 1;
-                if let Some(handler) = &self.config_handler {
-                    let _ = self.config.write_entry(handler);
+                self.save_config();
+            }
+            Message::SetAccountEnabled(enabled) => {
+                self.config.account_enabled = enabled;
+                if !enabled {
+                    self.clear_counts();
                 }
+                self.save_config();
+            }
+            Message::SetUseGhToken(enabled) => {
+                self.config.use_gh_token = enabled;
+                self.save_config();
             }
             Message::SetPatInput(input) => {
                 self.pat_input = input;
             }
             Message::SavePat => {
                 self.config.github_pat = self.pat_input.clone();
-                if let Some(handler) = &self.config_handler {
-                    let _ = self.config.write_entry(handler);
+                if self.save_config() {
+                    self.show_toast(fl!("saved"));
+                }
+            }
+            Message::SetTokenCommandInput(input) => {
+                self.token_command_input = input;
+            }
+            Message::SaveTokenCommand => {
+                self.config.token_command = self.token_command_input.trim().to_string();
+                if self.save_config() {
+                    self.show_toast(fl!("saved"));
+                }
+            }
+            Message::SetTerminalInput(input) => {
+                self.terminal_input = input;
+            }
+            Message::SaveTerminalCommand => {
+                self.config.terminal_command = self.terminal_input.trim().to_string();
+                if self.save_config() {
+                    self.show_toast(fl!("saved"));
+                }
+            }
+            Message::SetQueryPreset(idx) => {
+                if let Some(&query) = QUERY_PRESET_VALUES.get(idx) {
+                    self.config.search_query = query.to_string();
+                    self.query_input = self.config.search_query.clone();
+                    self.save_config();
+                }
+            }
+            Message::TabFetched(query, result) => {
+                self.tab_results.insert(query, result);
+            }
+            Message::SelectTab(entity) => {
+                self.tab_model.activate(entity);
+            }
+            Message::SetTabNameInput(input) => {
+                self.tab_name_input = input;
+            }
+            Message::SetTabQueryInput(input) => {
+                self.tab_query_input = input;
+            }
+            Message::AddTab => {
+                let name = self.tab_name_input.trim();
+                let query = self.tab_query_input.trim();
+                if name.is_empty() || query.is_empty() {
+                    return Task::none();
+                }
+                self.config.tabs.push(PopupTab {
+                    name: name.to_string(),
+                    query: query.to_string(),
+                });
+                self.tab_name_input.clear();
+                self.tab_query_input.clear();
+                self.save_config();
+                self.rebuild_tab_model();
+            }
+            Message::RemoveTab(idx) => {
+                if idx < self.config.tabs.len() {
+                    self.config.tabs.remove(idx);
+                    self.save_config();
+                    self.rebuild_tab_model();
+                }
+            }
+            Message::SetBadgeSumsTabs(enabled) => {
+                self.config.badge_sums_tabs = enabled;
+                self.save_config();
+            }
+            Message::SetBadgeAggregate(enabled) => {
+                self.config.badge_aggregate = enabled;
+                self.save_config();
+            }
+            Message::SetExtraMetric(slot, idx) => {
+                // Index 0 is "None", the rest follow the query presets.
+                let query = idx
+                    .checked_sub(1)
+                    .and_then(|idx| QUERY_PRESET_VALUES.get(idx))
+                    .map(|query| query.to_string());
+                let metrics = &mut self.config.extra_metrics;
+                match query {
+                    Some(query) if slot < metrics.len() => metrics[slot] = query,
+                    Some(query) => metrics.push(query),
+                    None if slot < metrics.len() => {
+                        metrics.remove(slot);
+                    }
+                    None => {}
+                }
+                let metrics = self.config.extra_metrics.clone();
+                self.extra_counts.retain(|query, _| metrics.contains(query));
+                self.save_config();
+            }
+            Message::SetQueryInput(input) => {
+                self.query_input = input;
+            }
+            Message::SaveQuery => {
+                let query = self.query_input.trim();
+                self.config.search_query = if query.is_empty() {
+                    SEARCH_QUERY.to_string()
+                } else {
+                    query.to_string()
+                };
+                self.query_input = self.config.search_query.clone();
+                if self.save_config() {
+                    self.show_toast(fl!("saved"));
+                }
+            }
+            Message::SetOrgInput(input) => {
+                self.org_input = input;
+                self.org_input_invalid = false;
+            }
+            Message::SaveOrg => {
+                let org = self.org_input.trim();
+                if !org.is_empty() && !is_valid_org(org) {
+                    self.org_input_invalid = true;
+                    return Task::none();
+                }
+                self.config.org_filter = (!org.is_empty()).then(|| org.to_string());
+                if self.save_config() {
+                    self.show_toast(fl!("saved"));
+                }
+            }
+            Message::SetApiVersionInput(input) => {
+                self.api_version_input = input;
+            }
+            Message::SaveApiVersion => {
+                self.config.api_version = self.api_version_input.trim().to_string();
+                if self.save_config() {
+                    self.show_toast(fl!("saved"));
+                }
+            }
+            Message::SetAllowInsecureTls(enabled) => {
+                self.config.allow_insecure_tls = enabled;
+                self.save_config();
+            }
+            Message::SetHttp1Only(enabled) => {
+                self.config.http1_only = enabled;
+                self.save_config();
+            }
+            Message::SetNoConnectionReuse(enabled) => {
+                self.config.no_connection_reuse = enabled;
+                self.save_config();
+            }
+            Message::SetCaPathInput(input) => {
+                self.ca_path_input = input;
+            }
+            Message::SaveCaPath => {
+                let path = self.ca_path_input.trim();
+                self.config.custom_ca_path = (!path.is_empty()).then(|| PathBuf::from(path));
+                if self.save_config() {
+                    self.show_toast(fl!("saved"));
+                }
+            }
+            Message::SetOutputsInput(input) => {
+                self.outputs_input = input;
+            }
+            Message::SaveOutputs => {
+                self.config.outputs = self
+                    .outputs_input
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|output| !output.is_empty())
+                    .map(String::from)
+                    .collect();
+                self.outputs_input = self.config.outputs.join(", ");
+                if self.save_config() {
+                    self.show_toast(fl!("saved"));
+                }
+            }
+            Message::SetLabelFormatInput(input) => {
+                self.label_format_input = input;
+            }
+            Message::SaveLabelFormat => {
+                if !self.label_format_input.contains("{count}") {
+                    self.show_toast(fl!("label-format-invalid"));
+                    return Task::none();
+                }
+                self.config.label_format = self.label_format_input.clone();
+                if self.save_config() {
+                    self.show_toast(fl!("saved"));
                 }
             }
+            Message::SetRealtimeUrlInput(input) => {
+                self.realtime_url_input = input;
+            }
+            Message::SaveRealtimeUrl => {
+                let url = self.realtime_url_input.trim();
+                let valid = url.is_empty()
+                    || url::Url::parse(url)
+                        .is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+                if !valid {
+                    self.show_toast(fl!("realtime-url-invalid"));
+                    return Task::none();
+                }
+                self.config.realtime_url = url.to_string();
+                if self.save_config() {
+                    self.show_toast(fl!("saved"));
+                }
+            }
+            Message::RealtimeEvent => {
+                self.poll_generation += 1;
+            }
             Message::SetPollInterval(idx) => {
                 if let Some(&secs) = POLL_VALUES.get(idx) {
                     self.config.poll_interval_secs = secs;
-                    if let Some(handler) = &self.config_handler {
-                        let _ = self.config.write_entry(handler);
-                    }
+                    self.save_config();
+                }
+            }
+            Message::SetForegroundInterval(idx) => {
+                if let Some(&secs) = FOREGROUND_POLL_VALUES.get(idx) {
+                    self.config.foreground_interval_secs = secs;
+                    self.save_config();
+                }
+            }
+            Message::SetAccurateCount(enabled) => {
+                self.config.accurate_count = enabled;
+                self.save_config();
+            }
+            Message::SetExcludeOwn(enabled) => {
+                self.config.exclude_own = enabled;
+                self.save_config();
+            }
+            Message::SetIncludeApproved(enabled) => {
+                self.config.include_approved = enabled;
+                self.save_config();
+            }
+            Message::SetTrackChangesRequested(enabled) => {
+                self.config.track_changes_requested = enabled;
+                if !enabled {
+                    self.changes_requested = None;
+                }
+                self.save_config();
+            }
+            Message::ResetPeak => {
+                // Starts over from the current count rather than from zero.
+                self.state.all_time_peak = self.pr_count.unwrap_or_default();
+                if let Some(handler) = &self.state_handler {
+                    let _ = self.state.write_entry(handler);
+                }
+            }
+            Message::SetTrackConflicts(enabled) => {
+                self.config.track_conflicts = enabled;
+                if !enabled {
+                    self.conflicting = None;
+                }
+                self.save_config();
+            }
+            Message::SetTerminalGhCommand(idx) => {
+                if let Some(&command) = TERMINAL_GH_VALUES.get(idx) {
+                    self.config.terminal_gh_command = command;
+                    self.save_config();
+                }
+            }
+            Message::SetTimeDisplay(idx) => {
+                if let Some(&mode) = TIME_DISPLAY_VALUES.get(idx) {
+                    self.config.time_display = mode;
+                    self.save_config();
+                }
+            }
+            Message::SetCountClick(idx) => {
+                if let Some(&action) = COUNT_CLICK_VALUES.get(idx) {
+                    self.config.count_click = action;
+                    self.save_config();
+                }
+            }
+            Message::SetShowCountHistory(enabled) => {
+                self.config.show_count_history = enabled;
+                self.save_config();
+            }
+            Message::SetShowDailyProgress(enabled) => {
+                self.config.show_daily_progress = enabled;
+                self.save_config();
+            }
+            Message::SetMinAge(idx) => {
+                if let Some(&hours) = MIN_AGE_VALUES.get(idx) {
+                    self.config.min_age_hours = hours;
+                    self.save_config();
+                }
+            }
+            Message::SetPrSort(idx) => {
+                if let Some(&sort) = SORT_VALUES.get(idx) {
+                    self.config.pr_sort = sort;
+                    self.save_config();
+                }
+            }
+            Message::SetPrListLimit(idx) => {
+                if let Some(&limit) = LIST_LIMIT_VALUES.get(idx) {
+                    self.config.pr_list_limit = limit;
+                    self.save_config();
+                }
+            }
+            Message::SetRepoVisibility(idx) => {
+                if let Some(&visibility) = VISIBILITY_VALUES.get(idx) {
+                    self.config.repo_visibility = visibility;
+                    self.save_config();
+                }
+            }
+            Message::SetBadgePalette(idx) => {
+                if let Some(&palette) = PALETTE_VALUES.get(idx) {
+                    self.config.badge_palette = palette;
+                    self.save_config();
+                }
+            }
+            Message::SetBadgeAccent(enabled) => {
+                self.config.badge_accent = enabled;
+                self.save_config();
+            }
+            Message::SetBadgeErrors(enabled) => {
+                self.config.badge_errors = enabled;
+                self.save_config();
+            }
+            Message::SetBadgeColorInput(tier, hex) => {
+                self.badge_color_inputs.insert(tier, hex);
+            }
+            Message::SaveBadgeColor(tier) => {
+                // Half-typed colours stay in the input; only empty or valid ones are saved.
+                let Some(hex) = self.badge_color_inputs.get(&tier) else {
+                    return Task::none();
+                };
+                let hex = hex.trim().to_string();
+                if !hex.is_empty() && parse_hex_color(&hex).is_none() {
+                    return Task::none();
+                }
+                self.badge_color_inputs.remove(&tier);
+                let field = match tier {
+                    BadgeTier::Error => &mut self.config.badge_color_error,
+                    BadgeTier::Zero => &mut self.config.badge_color_zero,
+                    BadgeTier::Normal => &mut self.config.badge_color_normal,
+                    BadgeTier::Warn => &mut self.config.badge_color_warn,
+                    BadgeTier::Critical => &mut self.config.badge_color_critical,
+                    BadgeTier::Unknown => &mut self.config.badge_color_unknown,
+                };
+                *field = hex;
+                self.save_config();
+            }
+            Message::SetRowField(field, enabled) => {
+                *field.flag(&mut self.config) = enabled;
+                self.save_config();
+            }
+            Message::SetAutohide(enabled) => {
+                self.config.autohide = enabled;
+                self.save_config();
+            }
+            Message::SetShowIcon(enabled) => {
+                self.config.show_icon = enabled;
+                self.save_config();
+            }
+            Message::SetStaleAfter(idx) => {
+                if let Some(&secs) = STALE_AFTER_VALUES.get(idx) {
+                    self.config.stale_after_secs = secs;
+                    self.save_config();
+                }
+            }
+            Message::SetDimDuringDnd(enabled) => {
+                self.config.dim_during_dnd = enabled;
+                self.save_config();
+            }
+            Message::DoNotDisturbChanged(enabled) => {
+                self.do_not_disturb = enabled;
+            }
+            Message::SetDimAfterFailures(idx) => {
+                if let Some(&failures) = DIM_AFTER_VALUES.get(idx) {
+                    self.config.dim_after_failures = failures;
+                    self.save_config();
+                }
+            }
+            Message::SetAutoOpenThreshold(idx) => {
+                if let Some(&threshold) = AUTO_OPEN_VALUES.get(idx) {
+                    self.config.auto_open_threshold = threshold;
+                    self.save_config();
+                }
+            }
+            Message::SetBadgeErrorDot(enabled) => {
+                self.config.badge_error_dot = enabled;
+                self.save_config();
+            }
+            Message::SetWorkingHoursEnabled(enabled) => {
+                self.config.working_hours_enabled = enabled;
+                self.save_config();
+            }
+            Message::SetWorkStart(hour) => {
+                self.config.work_start_hour = hour as u32;
+                self.save_config();
+            }
+            Message::SetWorkEnd(hour) => {
+                self.config.work_end_hour = hour as u32;
+                self.save_config();
+            }
+            Message::ToggleWorkDay(day, enabled) => {
+                self.config.work_days.retain(|&d| d != day);
+                if enabled {
+                    self.config.work_days.push(day);
+                }
+                self.save_config();
+            }
+            Message::SetNotifications(enabled) => {
+                self.config.notifications = enabled;
+                self.save_config();
+            }
+            Message::SetQuietStart(idx) => {
+                if let Some(&secs) = QUIET_START_VALUES.get(idx) {
+                    self.config.quiet_start_secs = secs;
+                    self.save_config();
                 }
             }
             Message::CheckGhStatus => {
+                // A check is still in flight; don't pile up `gh auth status` processes.
+                if self.gh_status.is_none() {
+                    return Task::none();
+                }
+                self.gh_status = None;
+                self.gh_check_id += 1;
+            }
+            Message::RunTestFetch => {
+                if self.test_fetch_running {
+                    return Task::none();
+                }
+                self.test_fetch_running = true;
+                let request = review_search(&self.config);
+                let description = request.describe();
+                return Task::perform(
+                    async move { github::fetch_pr_count(&request).await },
+                    move |result| {
+                        cosmic::Action::App(Message::TestFetchFinished(description.clone(), result))
+                    },
+                );
+            }
+            Message::TestFetchFinished(description, result) => {
+                self.test_fetch_running = false;
+                self.test_fetch = Some((description, result));
+            }
+            Message::ClearCache => {
+                let _ = cache::clear();
+                self.count_history.clear();
+            }
+            Message::ExpireToast => {
+                if self
+                    .transient_message
+                    .as_ref()
+                    .is_some_and(|(_, shown)| shown.elapsed() >= TOAST_DURATION)
+                {
+                    self.transient_message = None;
+                }
+            }
+            Message::PulseTick => {
+                if self
+                    .count_pulse_at
+                    .is_some_and(|started| started.elapsed() >= COUNT_PULSE)
+                {
+                    self.count_pulse_at = None;
+                }
+            }
+            Message::AnimationsChecked(enabled) => {
+                self.reduce_motion = !enabled;
+            }
+            Message::SetAnimateCount(enabled) => {
+                self.config.animate_count = enabled;
+                self.save_config();
+            }
+            Message::ReloadConfig => {
+                let Some(handler) = &self.config_handler else {
+                    return Task::none();
+                };
+                let config = Config::read(handler);
+                // Text inputs show what's on disk, even if they were being edited.
+                self.pat_input = config.github_pat.clone();
+                self.terminal_input = config.terminal_command.clone();
+                self.token_command_input = config.token_command.clone();
+                self.query_input = config.search_query.clone();
+                self.comparison_input = config.comparison_query.clone();
+                self.project_input = config.project.clone();
+                self.project_status_input = config.project_ignored_status.clone();
+                self.org_input = config.org_filter.clone().unwrap_or_default();
+                self.realtime_url_input = config.realtime_url.clone();
+                self.label_format_input = config.label_format.clone();
+                self.outputs_input = config.outputs.join(", ");
+                self.api_version_input = config.api_version.clone();
+                self.badge_color_inputs.clear();
+                self.ca_path_input = ca_path_text(&config);
+                // Restart the pollers for a fresh fetch, whether or not anything changed.
+                self.poll_generation += 1;
+                self.show_toast(fl!("config-reloaded"));
+                return self.update(Message::UpdateConfig(config));
+            }
+            Message::ConfigOpened(handler) => {
+                let Some(handler) = handler else {
+                    return Task::none();
+                };
+                let mut config = Config::read(&handler);
+                // A fresh install was marked as seen in memory only; save that now.
+                if config.last_seen_version.is_empty() && !self.config.last_seen_version.is_empty()
+                {
+                    config.last_seen_version = self.config.last_seen_version.clone();
+                    let _ = config.write_entry(&handler);
+                }
+                self.config_handler = Some(handler);
+                return self.update(Message::UpdateConfig(config));
+            }
+            Message::OpenConfigDir => {
+                if let Some(dir) = Config::dir(Self::APP_ID) {
+                    self.open_external(dir);
+                }
+            }
+            Message::CopyDiagnostics => {
+                let report = diagnostics::issue_report(&self.config, self.fetch_error.as_ref());
+                self.show_toast(fl!("copied"));
+                return cosmic::iced::clipboard::write(report);
+            }
+            Message::CopyCurlCommand => {
+                let command = review_search(&self.config).curl_command();
+                self.show_toast(fl!("copied"));
+                return cosmic::iced::clipboard::write(command);
+            }
+            Message::RequestReset => {
+                self.confirm_reset = true;
+            }
+            Message::CancelReset => {
+                self.confirm_reset = false;
+            }
+            Message::SetResetKeepPat(keep) => {
+                self.reset_keep_pat = keep;
+            }
+            Message::ConfirmReset => {
+                let mut config = Config::default();
+                if self.reset_keep_pat {
+                    config.github_pat = std::mem::take(&mut self.config.github_pat);
+                }
+                self.config = config;
+                self.save_config();
+                self.rebuild_tab_model();
+
+                // The poller is keyed on the config, so it restarts with the defaults.
+                self.pat_input = self.config.github_pat.clone();
+                self.terminal_input = self.config.terminal_command.clone();
+                self.token_command_input = self.config.token_command.clone();
+                self.query_input = self.config.search_query.clone();
+                self.comparison_input = self.config.comparison_query.clone();
+                self.project_input = self.config.project.clone();
+                self.project_status_input = self.config.project_ignored_status.clone();
+                self.org_input = String::new();
+                self.org_input_invalid = false;
+                self.realtime_url_input = String::new();
+                self.label_format_input = self.config.label_format.clone();
+                self.outputs_input = self.config.outputs.join(", ");
+                self.api_version_input = self.config.api_version.clone();
+                self.badge_color_inputs.clear();
+                self.ca_path_input = ca_path_text(&self.config);
+                self.clear_counts();
+                self.confirm_reset = false;
                 self.gh_status = None;
                 self.gh_check_id += 1;
             }
@@ -454,49 +2605,891 @@ impl cosmic::Application for AppModel {
 }
 
 impl AppModel {
-    /// Main popup view: shows PR count, error state, and action buttons.
-    fn main_view(&self) -> Element<'_, Message> {
-        let content_section: Element<_> = match (&self.fetch_error, self.pr_count) {
-            (Some(err), _) => widget::settings::section()
-                .add(widget::text::heading(fl!("error-label")))
-                .add(widget::text(err.clone()))
-                .into(),
-            (_, Some(count)) => widget::settings::section()
-                .add(widget::settings::item(
-                    fl!("pr-count-label"),
-                    widget::text(count.to_string()).size(28),
-                ))
-                .into(),
-            (_, None) => widget::settings::section()
-                .add(widget::text::body(fl!("loading")))
-                .into(),
+    /// Creates the popup; a pinned popup takes no input grab, so clicking elsewhere
+    /// leaves it open.
+    fn open_popup(&mut self) -> Task<cosmic::Action<Message>> {
+        let new_id = Id::unique();
+        self.popup.replace(new_id);
+        let mut popup_settings = self.core.applet.get_popup_settings(
+            self.core.main_window_id().unwrap(),
+            new_id,
+            None,
+            None,
+            None,
+        );
+        // The settings page is wider, to fit its section list beside the sections.
+        let max_width = if self.show_settings { SETTINGS_POPUP_WIDTH } else { 300.0 };
+        popup_settings.positioner.size_limits = Limits::NONE
+            .max_width(max_width)
+            .min_width(220.0)
+            .min_height(80.0)
+            .max_height(500.0);
+        popup_settings.grab = !self.popup_pinned;
+        get_popup(popup_settings)
+    }
+
+    /// Swaps the open popup for a new one, picking up settings that are fixed when a popup
+    /// is created: its grab and its width.
+    fn reopen_popup(&mut self) -> Task<cosmic::Action<Message>> {
+        match self.popup.take() {
+            Some(p) => Task::batch([destroy_popup(p), self.open_popup()]),
+            None => Task::none(),
+        }
+    }
+
+    /// Writes the config back, reporting a failure in a toast. Returns whether it was saved.
+    fn save_config(&mut self) -> bool {
+        let Some(handler) = &self.config_handler else {
+            return false;
         };
+        match self.config.write_entry(handler) {
+            Ok(()) => true,
+            Err(why) => {
+                self.show_toast(fl!("config-save-failed", error = why.to_string()));
+                false
+            }
+        }
+    }
 
-        let actions: Element<_> = widget::row()
-            .push(
-                widget::button::suggested(fl!("open-github")).on_press(Message::OpenGitHub),
-            )
-            .push(widget::horizontal_space())
-            .push(widget::button::standard(fl!("settings")).on_press(Message::OpenSettings))
-            .into();
+    /// Whether the last successful fetch is older than the stale threshold. Outside working
+    /// hours or while paused nothing is fetched, so the count isn't expected to be fresh.
+    fn count_is_stale(&self) -> bool {
+        if self.config.stale_after_secs == 0
+            || self.pr_count.is_none()
+            || !self.config.account_enabled
+            || !self.is_working_time()
+        {
+            return false;
+        }
+        let threshold = Duration::from_secs(self.config.stale_after_secs);
+        match self.last_success_at {
+            Some(fetched) => (chrono::Utc::now() - fetched)
+                .to_std()
+                .is_ok_and(|age| age > threshold),
+            // Only the cached count so far.
+            None => self.started_at.elapsed() > threshold,
+        }
+    }
 
-        widget::column()
-            .push(
-                widget::column()
-                    .push(content_section)
-                    .push(actions)
-                    .spacing(8)
-                    .padding(12),
-            )
-            .into()
+    /// Whether the panel this instance runs in is on one of the outputs picked in settings.
+    fn on_chosen_output(&self) -> bool {
+        self.config.outputs.is_empty()
+            || self.config.outputs.contains(&self.core.applet.output_name)
     }
 
-    /// Settings popup view: auth method selection and method-specific options.
-    fn settings_view(&self) -> Element<'_, Message> {
-        // Header: back button + page title
-        let header: Element<_> = widget::row()
-            .push(
-                widget::button::text(fl!("back"))
+    /// Shows a short message at the bottom of the popup until [`TOAST_DURATION`] passes.
+    fn show_toast(&mut self, message: String) {
+        self.transient_message = Some((message, Instant::now()));
+    }
+
+    /// Opens a URL or path with the desktop's default handler.
+    fn open_external(&mut self, target: impl AsRef<std::ffi::OsStr>) {
+        if let Err(why) = std::process::Command::new("xdg-open").arg(target).spawn() {
+            self.show_toast(fl!("open-failed", error = why.to_string()));
+        }
+    }
+
+    /// Switches the popup to the settings page and starts a fresh gh status check. A
+    /// managed config can't be edited, so its settings page never opens.
+    fn open_settings(&mut self) {
+        if self.managed {
+            return;
+        }
+        self.show_settings = true;
+        self.settings_section = None;
+        self.confirm_reset = false;
+        self.gh_status = None;
+        self.gh_check_id += 1;
+    }
+
+    /// Notifies about a freshly fetched count when notifications are enabled.
+    ///
+    /// Normally only increases are announced. After a gap much longer than the poll
+    /// interval (the machine was suspended) or when the previous count came from the
+    /// cache, any change is summed up in one "while away" notification instead.
+    fn notify_count_change(&mut self, count: u64) {
+        let now = SystemTime::now();
+        // Monotonic clocks stop during suspend, so the gap is measured in wall-clock time.
+        let longest_interval = self
+            .config
+            .poll_interval_secs
+            .max(self.config.foreground_interval_secs);
+        let expected_gap = Duration::from_secs(longest_interval * 2 + 30);
+        let resumed = self
+            .last_poll_at
+            .and_then(|last| now.duration_since(last).ok())
+            .is_some_and(|gap| gap > expected_gap);
+        self.last_poll_at = Some(now);
+
+        let Some(previous) = self.pr_count else {
+            return;
+        };
+        if !self.config.notifications
+            || self.do_not_disturb
+            || count == previous
+            || !self.is_working_time()
+        {
+            return;
+        }
+        // The backlog found right after login updates the badge without a notification.
+        if self.started_at.elapsed() < Duration::from_secs(self.config.quiet_start_secs) {
+            return;
+        }
+
+        if resumed || self.count_is_cached {
+            send_notification(&fl!("app-title"), &fl!("notify-while-away", count = count));
+        } else if count > previous {
+            send_notification(&fl!("app-title"), &fl!("notify-waiting", count = count));
+        }
+    }
+
+    /// Opens the review queue in the browser when the count rises past the configured
+    /// threshold. Called before `pr_count` is updated.
+    fn auto_open(&mut self, count: u64) {
+        let (Some(threshold), Some(previous)) = (self.config.auto_open_threshold, self.pr_count)
+        else {
+            return;
+        };
+        let threshold = u64::from(threshold);
+        if previous >= threshold
+            || count < threshold
+            || self.do_not_disturb
+            || !self.is_working_time()
+        {
+            return;
+        }
+        if self.started_at.elapsed() < Duration::from_secs(self.config.quiet_start_secs)
+            || self.auto_opened_at.is_some_and(|at| at.elapsed() < AUTO_OPEN_COOLDOWN)
+        {
+            return;
+        }
+        self.auto_opened_at = Some(Instant::now());
+        let query = main_query(&self.config);
+        self.open_external(github::web_search_url(github::API_BASE_URL, &query));
+    }
+
+    /// Remembers request stats for the diagnostics section.
+    fn record_fetch_stats(&mut self, stats: FetchStats) {
+        self.last_fetch_stats = Some(stats);
+        if self.fetch_durations.len() == FETCH_HISTORY_LEN {
+            self.fetch_durations.pop_front();
+        }
+        self.fetch_durations.push_back(stats.elapsed);
+    }
+
+    /// Count shown on the main badge: the main search, plus the extra metrics when they
+    /// are summed up. Extra metrics that failed or haven't been fetched count as zero.
+    fn badge_count(&self) -> Option<u64> {
+        let mut count = self.pr_count?;
+        if self.config.badge_sums_tabs {
+            count += self
+                .config
+                .tabs
+                .iter()
+                .filter_map(|tab| self.tab_results.get(&tab.query)?.as_ref().ok())
+                .map(|results| results.total_count)
+                .sum::<u64>();
+        }
+        if !self.config.badge_aggregate {
+            return Some(count);
+        }
+        let extras: u64 = self
+            .config
+            .extra_metrics
+            .iter()
+            .take(MAX_EXTRA_METRICS)
+            .filter_map(|query| self.extra_counts.get(query)?.as_ref().ok())
+            .sum();
+        Some(count + extras)
+    }
+
+    /// Whether every count is a successful zero and no popup is open.
+    fn fully_idle(&self) -> bool {
+        self.popup.is_none()
+            && self.fetch_error.is_none()
+            && self.pr_count == Some(0)
+            && self
+                .config
+                .extra_metrics
+                .iter()
+                .take(MAX_EXTRA_METRICS)
+                .all(|query| matches!(self.extra_counts.get(query), Some(Ok(0))))
+            && self.config.tabs.iter().all(|tab| {
+                matches!(self.tab_results.get(&tab.query), Some(Ok(r)) if r.total_count == 0)
+            })
+    }
+
+    /// Rebuilds the popup's tab bar from the config, selecting the main search.
+    fn rebuild_tab_model(&mut self) {
+        let mut model = segmented_button::SingleSelectModel::default();
+        model.insert().text(fl!("tab-main")).data(0usize).activate();
+        for (idx, tab) in self.config.tabs.iter().enumerate() {
+            model.insert().text(tab.name.clone()).data(idx + 1);
+        }
+        self.tab_model = model;
+        let queries: Vec<&str> = self.config.tabs.iter().map(|tab| tab.query.as_str()).collect();
+        self.tab_results.retain(|query, _| queries.contains(&query.as_str()));
+    }
+
+    /// The popup tab being shown, unless it's the main search.
+    fn active_tab(&self) -> Option<&PopupTab> {
+        let idx = *self.tab_model.active_data::<usize>()?;
+        self.config.tabs.get(idx.checked_sub(1)?)
+    }
+
+    /// Count and PRs of one popup tab.
+    fn tab_view<'a>(&'a self, tab: &'a PopupTab) -> Element<'a, Message> {
+        let mut section = widget::settings::section();
+        match self.tab_results.get(&tab.query) {
+            None => section = section.add(widget::text::body(fl!("loading"))),
+            Some(Err(err)) => {
+                section = section
+                    .add(widget::text::heading(fl!("error-label")))
+                    .add(widget::text(err.to_string()));
+            }
+            Some(Ok(results)) => {
+                section = section.add(widget::settings::item(
+                    tab.name.clone(),
+                    widget::text(format_count(results.total_count)).size(28),
+                ));
+                let limit = self.config.pr_list_limit;
+                for pr in results.items.iter().take(limit) {
+                    let opened = format_time(pr.created_at, self.config.time_display);
+                    section = section.add(
+                        widget::column()
+                            .push(
+                                widget::button::link(pr.title.clone())
+                                    .on_press(Message::OpenUrl(pr.html_url.clone())),
+                            )
+                            .push(widget::text::caption(format!(
+                                "{}#{} · {opened}",
+                                pr.repo(),
+                                pr.number
+                            ))),
+                    );
+                }
+                let shown = results.items.len().min(limit) as u64;
+                if results.total_count > shown {
+                    section = section.add(
+                        widget::button::link(fl!(
+                            "more-prs",
+                            count = results.total_count - shown
+                        ))
+                        .on_press(Message::OpenSearch(tab.query.clone())),
+                    );
+                }
+            }
+        }
+        section.into()
+    }
+
+    /// Per-metric counts behind a summed-up badge.
+    fn metric_breakdown(&self) -> Option<Element<'_, Message>> {
+        if !self.config.badge_aggregate || self.config.extra_metrics.is_empty() {
+            return None;
+        }
+
+        let mut section = widget::settings::section().title(fl!("badge-aggregate-breakdown"));
+        for query in self.config.extra_metrics.iter().take(MAX_EXTRA_METRICS) {
+            let label = QUERY_PRESET_VALUES
+                .iter()
+                .position(|&preset| preset == query)
+                .map_or_else(|| query.clone(), |idx| QUERY_PRESET_LABELS[idx].to_string());
+            let value = match self.extra_counts.get(query) {
+                Some(Ok(count)) => format_count(*count),
+                Some(Err(_)) => "!".to_string(),
+                None => "…".to_string(),
+            };
+            section = section.add(widget::settings::item(
+                label,
+                widget::button::text(value).on_press(Message::OpenSearch(query.clone())),
+            ));
+        }
+        if let Some(total) = self.badge_count() {
+            section = section.add(widget::settings::item(
+                fl!("badge-aggregate-total"),
+                widget::text::heading(format_count(total)),
+            ));
+        }
+        Some(section.into())
+    }
+
+    /// The main count next to the comparison search's, e.g. "3 pending / 7 done";
+    /// clicking it opens the comparison search.
+    fn comparison_view(&self) -> Option<Element<'_, Message>> {
+        let query = comparison_query(&self.config)?;
+        let pending = self.pr_count?;
+        let done = match &self.comparison_count {
+            Some(Ok(count)) => format_count(*count),
+            Some(Err(_)) => "!".to_string(),
+            None => "…".to_string(),
+        };
+        Some(
+            widget::settings::section()
+                .add(
+                    widget::button::text(fl!(
+                        "comparison-summary",
+                        pending = format_count(pending),
+                        done = done
+                    ))
+                    .on_press(Message::OpenSearch(query)),
+                )
+                .into(),
+        )
+    }
+
+    /// Popup text for the main count. A paged count that reached the search API's result
+    /// limit is only a lower bound.
+    fn count_text(&self, count: u64) -> String {
+        if self.config.accurate_count && count >= u64::from(github::SEARCH_RESULT_LIMIT) {
+            format!("{}+", format_count(count))
+        } else {
+            format_count(count)
+        }
+    }
+
+    /// Highlights of the releases since the notes were last dismissed, shown until they
+    /// are dismissed again.
+    fn whats_new(&self) -> Option<Element<'_, Message>> {
+        let seen = &self.config.last_seen_version;
+        let items: Vec<String> = release_notes()
+            .into_iter()
+            .filter(|(version, _)| {
+                is_newer_version(version, seen) && !is_newer_version(version, APP_VERSION)
+            })
+            .map(|(_, item)| item)
+            .collect();
+        if items.is_empty() {
+            return None;
+        }
+
+        let mut section = widget::settings::section().title(fl!("whats-new-title"));
+        for item in items {
+            section = section.add(widget::text::body(item));
+        }
+        section = section.add(
+            widget::row()
+                .push(
+                    widget::button::standard(fl!("whats-new-open-settings"))
+                        .on_press(Message::OpenSettings),
+                )
+                .push(widget::horizontal_space())
+                .push(widget::button::text(fl!("dismiss")).on_press(Message::DismissWhatsNew))
+                .spacing(8),
+        );
+        Some(section.into())
+    }
+
+    /// The longest-waiting of the fetched PRs, whatever order the list is shown in.
+    fn oldest_pr(&self) -> Option<&PullRequest> {
+        self.pull_requests.iter().min_by_key(|pr| pr.created_at)
+    }
+
+    /// Whether badges and markers use the high-contrast palette.
+    fn uses_high_contrast(&self) -> bool {
+        match self.config.badge_palette {
+            BadgePalette::Auto => cosmic::theme::active().cosmic().is_high_contrast,
+            BadgePalette::Standard => false,
+            BadgePalette::HighContrast => true,
+        }
+    }
+
+    /// Days until the PAT expires, rounded up, once that is within
+    /// [`TOKEN_EXPIRY_WARNING_DAYS`]; zero or less once it has expired. Only in PAT mode,
+    /// where the expiry is reported.
+    fn pat_expiry_days(&self) -> Option<i64> {
+        if self.config.auth_method != AuthMethod::Pat {
+            return None;
+        }
+        let remaining = self.state.pat_expires_at? - chrono::Utc::now();
+        (remaining <= chrono::TimeDelta::days(TOKEN_EXPIRY_WARNING_DAYS))
+            .then(|| (remaining.num_seconds() + 86_399).div_euclid(86_400))
+    }
+
+    /// Whether it is currently within the configured working hours.
+    fn is_working_time(&self) -> bool {
+        self.config.is_working_time(chrono::Local::now())
+    }
+
+    /// Whether the configured auth method can be expected to work: a PAT is saved, or
+    /// gh hasn't reported that it is logged out.
+    fn has_usable_auth(&self) -> bool {
+        match self.config.auth_method {
+            AuthMethod::Pat => {
+                self.config.use_gh_token
+                    || !self.config.auth_backend.is_empty()
+                    || !self.config.token_command.is_empty()
+                    || !self.config.github_pat.is_empty()
+            }
+            AuthMethod::GhCli => !self
+                .fetch_error
+                .as_ref()
+                .is_some_and(|err| err.kind == ErrorKind::Unauthorized),
+        }
+    }
+
+    /// Forgets all fetched results, e.g. when polling stops or the config is reset.
+    fn clear_counts(&mut self) {
+        self.pr_count = None;
+        self.count_is_cached = false;
+        self.pull_requests.clear();
+        self.count_incomplete = false;
+        self.fetch_error = None;
+        self.changes_requested = None;
+        self.conflicting = None;
+        self.project_count = None;
+        self.extra_counts.clear();
+        self.comparison_count = None;
+        self.tab_results.clear();
+    }
+
+    /// Main popup view: shows PR count, error state, and action buttons.
+    fn main_view(&self) -> Element<'_, Message> {
+        let content_section: Element<_> = match (&self.fetch_error, self.pr_count) {
+            (Some(err), _) => {
+                // Verbose errors are cut down to a few lines; the expander shows them whole.
+                let message = err.to_string();
+                let (preview, truncated) = error_preview(&message);
+                let mut section = widget::settings::section()
+                    .add(widget::text::heading(fl!("error-label")))
+                    .add(wrapped_text(
+                        if self.show_error_details { message } else { preview },
+                        14,
+                    ));
+
+                if truncated || err.details.is_some() {
+                    let toggle_label = if self.show_error_details {
+                        fl!("hide-details")
+                    } else {
+                        fl!("show-details")
+                    };
+                    section = section.add(
+                        widget::button::text(toggle_label).on_press(Message::ToggleErrorDetails),
+                    );
+                }
+                if let Some(details) = err.details.clone().filter(|_| self.show_error_details) {
+                    section = section.add(wrapped_text(details, 12));
+                }
+
+                section.into()
+            }
+            (_, Some(count)) => {
+                // The count is the biggest thing in the popup, so it is a button too; the
+                // text style only shows that on hover.
+                let on_click = match self.config.count_click {
+                    CountClickAction::OpenGitHub => Some(Message::OpenGitHub),
+                    CountClickAction::OpenOldest => self.oldest_pr().map(|_| Message::OpenOldest),
+                    CountClickAction::Nothing => None,
+                };
+                let count_button = widget::button::custom(
+                    widget::text(self.count_text(count)).size(28),
+                )
+                .class(cosmic::theme::Button::Text)
+                .on_press_maybe(on_click);
+                let mut section = widget::settings::section()
+                    .add(widget::settings::item(fl!("pr-count-label"), count_button));
+                if !self.is_working_time() {
+                    section = section.add(widget::text::caption(fl!("outside-working-hours")));
+                } else if self.count_is_cached {
+                    section = section.add(widget::text::caption(fl!("cached-count")));
+                } else if let Some(fetched) = self.last_success_at {
+                    section = section.add(widget::text::caption(fl!(
+                        "updated-at",
+                        time = format_time(fetched, self.config.time_display)
+                    )));
+                }
+                if self.count_incomplete && !self.count_is_cached {
+                    section = section.add(widget::text::caption(fl!("count-incomplete")));
+                }
+                if self.count_is_stale() {
+                    section = section.add(widget::text::caption(fl!("count-stale")));
+                }
+                section.into()
+            }
+            (_, None) if !self.config.account_enabled => widget::settings::section()
+                .add(widget::text::body(fl!("account-paused")))
+                .into(),
+            (_, None) => widget::settings::section()
+                .add(widget::text::body(fl!("loading")))
+                .into(),
+        };
+
+        let changes_requested_section: Option<Element<_>> =
+            self.changes_requested.as_ref().map(|result| {
+                let value = match result {
+                    Ok(&count) => format_count(count),
+                    Err(_) => "!".to_string(),
+                };
+                widget::settings::section()
+                    .add(widget::settings::item(
+                        fl!("changes-requested-label"),
+                        widget::text(value).size(20),
+                    ))
+                    .into()
+            });
+
+        let conflicts_section: Option<Element<_>> = self.conflicting.as_ref().map(|result| {
+            let value = match result {
+                Ok(&count) => format_count(count),
+                Err(_) => "!".to_string(),
+            };
+            widget::settings::section()
+                .add(widget::settings::item(
+                    fl!("conflicts-label"),
+                    widget::row()
+                        .push(widget::text(value).size(20))
+                        .push(
+                            widget::button::text(fl!("open-authored"))
+                                .on_press(Message::OpenSearch(AUTHORED_QUERY.to_string())),
+                        )
+                        .spacing(8)
+                        .align_y(Alignment::Center),
+                ))
+                .into()
+        });
+
+        let project_section: Option<Element<_>> = self.project_count.as_ref().map(|result| {
+            let value = match result {
+                Ok(&count) => format_count(count),
+                Err(_) => "!".to_string(),
+            };
+            // A node ID has no page to open.
+            let open = self
+                .config
+                .project
+                .starts_with("https://")
+                .then(|| Message::OpenUrl(self.config.project.clone()));
+            widget::settings::section()
+                .add(widget::settings::item(
+                    fl!("project-count-label"),
+                    widget::row()
+                        .push(widget::text(value).size(20))
+                        .push(widget::button::text(fl!("open-project")).on_press_maybe(open))
+                        .spacing(8)
+                        .align_y(Alignment::Center),
+                ))
+                .into()
+        });
+
+        let mut actions = widget::row()
+            .push(
+                widget::button::suggested(fl!("open-github")).on_press(Message::OpenGitHub),
+            )
+            .spacing(8);
+
+        // The terminal action runs `gh`, so it is only offered in GitHub CLI mode.
+        if matches!(self.config.auth_method, AuthMethod::GhCli) {
+            actions = actions.push(
+                widget::button::standard(fl!("open-terminal")).on_press(Message::OpenTerminal),
+            );
+        }
+
+        let actions: Element<_> = actions
+            .push(widget::horizontal_space())
+            .push(
+                widget::button::standard(fl!("settings"))
+                    .on_press_maybe((!self.managed).then_some(Message::OpenSettings)),
+            )
+            .into();
+
+        let mut body = widget::column();
+        if self.managed {
+            body = body.push(widget::text::caption(fl!("managed-notice")));
+        } else if let Some(whats_new) = self.whats_new() {
+            body = body.push(whats_new);
+        }
+        if !self.config.tabs.is_empty() {
+            body = body.push(
+                widget::segmented_control::horizontal(&self.tab_model)
+                    .on_activate(Message::SelectTab),
+            );
+        }
+        if let Some(tab) = self.active_tab() {
+            return widget::column()
+                .push(body.push(self.tab_view(tab)).push(actions).spacing(8).padding(12))
+                .into();
+        }
+        body = body.push(content_section);
+        if self.fetch_error.is_none() {
+            if let Some(breakdown) = self.metric_breakdown() {
+                body = body.push(breakdown);
+            }
+            if let Some(comparison) = self.comparison_view() {
+                body = body.push(comparison);
+            }
+        }
+        if self.config.show_daily_progress && self.fetch_error.is_none() {
+            if let Some(progress) = self.daily_progress() {
+                body = body.push(progress);
+            }
+        }
+        if self.config.show_count_history && self.fetch_error.is_none() {
+            if let Some(sparkline) = self.count_sparkline() {
+                body = body.push(sparkline);
+            }
+        }
+        if self.fetch_error.is_none() {
+            if let Some(oldest) = self.oldest_pr() {
+                body = body.push(
+                    widget::button::standard(fl!("review-oldest", title = oldest.title.clone()))
+                        .on_press(Message::OpenOldest)
+                        .width(cosmic::iced::Length::Fill),
+                );
+            }
+        }
+        if self.fetch_error.is_none() && !self.pull_requests.is_empty() {
+            body = body.push(self.pr_list());
+        }
+        if let Some(section) = changes_requested_section {
+            body = body.push(section);
+        }
+        if let Some(section) = conflicts_section {
+            body = body.push(section);
+        }
+        if let Some(section) = project_section {
+            body = body.push(section);
+        }
+
+        widget::column()
+            .push(body.push(actions).spacing(8).padding(12))
+            .into()
+    }
+
+    /// Progress bar that fills as the count drops from the day's peak towards zero.
+    fn daily_progress(&self) -> Option<Element<'_, Message>> {
+        let count = self.pr_count?;
+        let peak = self.state.daily_peak.max(count);
+        if peak == 0 {
+            return None;
+        }
+
+        let cleared = peak - count;
+        Some(
+            widget::settings::section()
+                .add(widget::text::body(fl!("daily-remaining", count = format_count(count))))
+                .add(cosmic::iced::widget::progress_bar(
+                    0.0..=peak as f32,
+                    cleared as f32,
+                ))
+                .add(widget::text::caption(fl!(
+                    "daily-cleared",
+                    cleared = format_count(cleared),
+                    peak = format_count(peak)
+                )))
+                .into(),
+        )
+    }
+
+    /// Bar sparkline of the recent counts, scaled to the highest one.
+    fn count_sparkline(&self) -> Option<Element<'_, Message>> {
+        let (since, _) = self.count_history.front()?;
+        if self.count_history.len() < 2 {
+            return None;
+        }
+
+        let peak = self.count_history.iter().map(|&(_, count)| count).max()?;
+        let scale = peak.max(1) as f32;
+        let color: Color = cosmic::theme::active().cosmic().accent_color().into();
+        let bars = self.count_history.iter().fold(
+            widget::row().spacing(1).align_y(Alignment::End),
+            |bars, &(_, count)| {
+                // Keep a sliver for zero so the timeline stays visible.
+                let height = (SPARKLINE_HEIGHT * count as f32 / scale).max(1.0);
+                bars.push(
+                    widget::container(widget::Space::new(0, 0))
+                        .width(cosmic::iced::Length::Fill)
+                        .height(height)
+                        .class(cosmic::theme::Container::Custom(Box::new(move |_| {
+                            cosmic::iced_widget::container::Style {
+                                background: Some(cosmic::iced::Background::Color(color)),
+                                ..Default::default()
+                            }
+                        }))),
+                )
+            },
+        );
+
+        Some(
+            widget::settings::section()
+                .add(widget::container(bars).height(SPARKLINE_HEIGHT))
+                .add(widget::text::caption(fl!(
+                    "count-history-since",
+                    time = since.format("%H:%M").to_string(),
+                    peak = format_count(peak)
+                )))
+                .into(),
+        )
+    }
+
+    /// List of the fetched PRs; each title opens the PR in the browser.
+    ///
+    /// Consecutive PRs from the same repository are grouped under a header; clicking a
+    /// header narrows the list to that repository until the filter is cleared.
+    fn pr_list(&self) -> Element<'_, Message> {
+        let limit = self.config.pr_list_limit;
+        let high_contrast = self.uses_high_contrast();
+        let mut section = widget::settings::section();
+
+        if let Some(repo) = &self.repo_filter {
+            let count = self.pull_requests.iter().filter(|pr| pr.repo() == repo).count();
+            section = section.add(
+                widget::button::standard(fl!(
+                    "clear-repo-filter",
+                    repo = repo.clone(),
+                    count = format_count(count as u64)
+                ))
+                .on_press(Message::SetRepoFilter(None)),
+            );
+        }
+
+        let shown: Vec<&PullRequest> = self
+            .pull_requests
+            .iter()
+            .filter(|pr| self.repo_filter.as_deref().is_none_or(|repo| pr.repo() == repo))
+            .take(limit)
+            .collect();
+        // PRs that arrived since the popup was last opened go first, under a divider of
+        // their own, when there are older ones to set them apart from.
+        let (new, earlier): (Vec<_>, Vec<_>) =
+            shown.iter().copied().partition(|pr| !self.seen_at_open.contains(&pr.id));
+        let groups = if new.is_empty() || earlier.is_empty() {
+            vec![(None, shown)]
+        } else {
+            vec![(Some(fl!("pr-new")), new), (Some(fl!("pr-earlier")), earlier)]
+        };
+
+        for (heading, prs) in groups {
+            let mark_new = heading.is_none();
+            if let Some(heading) = heading {
+                section = section.add(
+                    widget::row()
+                        .push(widget::text::caption_heading(heading))
+                        .push(widget::divider::horizontal::default())
+                        .spacing(8)
+                        .align_y(Alignment::Center),
+                );
+            }
+            let mut current_repo = None;
+            for pr in prs {
+                if self.repo_filter.is_none() && current_repo != Some(pr.repo()) {
+                    current_repo = Some(pr.repo());
+                    section = section.add(
+                        widget::button::text(pr.repo().to_string())
+                            .on_press(Message::SetRepoFilter(Some(pr.repo().to_string()))),
+                    );
+                }
+                section = section.add(self.pr_row(pr, high_contrast, mark_new));
+            }
+        }
+        if let Some(err) = &self.pr_statuses_error {
+            section = section.add(widget::text::caption(fl!(
+                "pr-status-error",
+                error = err.category()
+            )));
+        }
+
+        if self.repo_filter.is_some() {
+            return section.into();
+        }
+
+        // The rest are only a click away in the full GitHub search.
+        let total = self.pr_count.map_or(0, |count| count as usize);
+        let limit_hit = if self.config.accurate_count {
+            total >= github::SEARCH_RESULT_LIMIT as usize
+        } else {
+            total > github::SEARCH_RESULT_LIMIT as usize
+        };
+        if limit_hit {
+            section = section.add(widget::text::caption(fl!(
+                "search-limit-hit",
+                limit = github::SEARCH_RESULT_LIMIT
+            )));
+        }
+        let shown = self.pull_requests.len().min(limit);
+        if total > shown {
+            section = section.add(
+                widget::button::link(fl!("more-prs", count = total - shown))
+                    .on_press(Message::OpenGitHub),
+            );
+        }
+
+        section.into()
+    }
+
+    /// One row of the PR list: the linked title and the details picked in settings.
+    /// `mark_new` tags unseen PRs, unless a "New" heading already sets them apart.
+    fn pr_row<'a>(
+        &'a self,
+        pr: &'a PullRequest,
+        high_contrast: bool,
+        mark_new: bool,
+    ) -> Element<'a, Message> {
+        // Without the title, the PR's number has to carry the link.
+        let config = &self.config;
+        let link_label = if config.row_show_title {
+            pr.title.clone()
+        } else if config.row_show_repo {
+            format!("{}#{}", pr.repo(), pr.number)
+        } else {
+            format!("#{}", pr.number)
+        };
+        let title = widget::button::link(link_label)
+            .on_press(Message::OpenUrl(pr.html_url.clone()));
+        let status = self.pr_statuses.get(&pr.node_id);
+        let review_state = status
+            .map(|status| status.review)
+            .filter(|_| config.row_show_review_state);
+        let title: Element<_> = match review_state {
+            Some(state) => widget::row()
+                .push(
+                    widget::container(widget::Space::new(0, 0))
+                        .width(8)
+                        .height(8)
+                        .class(badge_style(review_state_color(state, high_contrast))),
+                )
+                .push(title)
+                .spacing(6)
+                .align_y(Alignment::Center)
+                .into(),
+            None => title.into(),
+        };
+        let mut details = Vec::new();
+        if config.row_show_title {
+            details.push(if config.row_show_repo {
+                format!("{}#{}", pr.repo(), pr.number)
+            } else {
+                format!("#{}", pr.number)
+            });
+        }
+        if config.row_show_author {
+            details.push(pr.user.login.clone());
+        }
+        if config.row_show_age {
+            details.push(format_time(pr.created_at, config.time_display));
+        }
+        if mark_new && !self.seen_at_open.contains(&pr.id) {
+            details.push(fl!("pr-new"));
+        }
+        let mut row = widget::column().push(title);
+        if !details.is_empty() {
+            row = row.push(widget::text::caption(details.join(" · ")));
+        }
+        if config.row_show_ci && status.is_some_and(|status| status.ci_failing) {
+            row = row.push(
+                widget::button::link(fl!("open-ci")).on_press(Message::OpenUrl(pr.checks_url())),
+            );
+        }
+        row.into()
+    }
+
+    /// Settings popup view: auth method selection and method-specific options.
+    fn settings_view(&self) -> Element<'_, Message> {
+        // Header: back button + page title. It stays put, with the section list, while
+        // the sections beside it scroll.
+        let header: Element<_> = widget::row()
+            .push(
+                widget::button::text(fl!("back"))
+                    .id(SETTINGS_BACK_ID.clone())
                     .on_press(Message::CloseSettings),
             )
             .push(widget::text::heading(fl!("settings")))
@@ -507,6 +3500,10 @@ impl AppModel {
         // Auth method section with radio buttons
         let auth_section: Element<_> = widget::settings::section()
             .title(fl!("auth-method-label"))
+            .add(widget::settings::item(
+                fl!("account-enabled-label"),
+                widget::toggler(self.config.account_enabled).on_toggle(Message::SetAccountEnabled),
+            ))
             .add(widget::settings::item(
                 fl!("auth-gh-cli"),
                 widget::radio(
@@ -540,54 +3537,947 @@ impl AppModel {
                     .add(
                         widget::row()
                             .push(widget::horizontal_space())
-                            .push(
+                            .push(if self.gh_status.is_some() {
                                 widget::button::standard(fl!("check-again"))
-                                    .on_press(Message::CheckGhStatus),
+                                    .on_press(Message::CheckGhStatus)
+                            } else {
+                                // Disabled until GhStatusFetched arrives.
+                                widget::button::standard(fl!("gh-checking"))
+                            }),
+                    )
+                    .add(widget::text::body(fl!("terminal-command-label")))
+                    .add(
+                        widget::text_input("cosmic-term -e", &self.terminal_input)
+                            .on_input(Message::SetTerminalInput),
+                    )
+                    .add(
+                        widget::row()
+                            .push(widget::horizontal_space())
+                            .push(
+                                widget::button::suggested(fl!("save"))
+                                    .on_press(Message::SaveTerminalCommand),
                             ),
                     )
+                    .add(widget::settings::item(
+                        fl!("terminal-gh-label"),
+                        widget::dropdown(
+                            TERMINAL_GH_LABELS,
+                            TERMINAL_GH_VALUES
+                                .iter()
+                                .position(|&c| c == self.config.terminal_gh_command),
+                            Message::SetTerminalGhCommand,
+                        ),
+                    ))
                     .into()
             }
-            AuthMethod::Pat => widget::settings::section()
-                .title(fl!("pat-label"))
-                .add(
-                    widget::text_input("ghp_...", &self.pat_input)
-                        .on_input(Message::SetPatInput),
-                )
-                .add(
-                    widget::row()
-                        .push(widget::horizontal_space())
-                        .push(
-                            widget::button::suggested(fl!("save"))
-                                .on_press(Message::SavePat),
-                        ),
-                )
-                .into(),
+            AuthMethod::Pat => {
+                let mut section = widget::settings::section()
+                    .title(fl!("pat-label"))
+                    .add(widget::settings::item(
+                        fl!("use-gh-token-label"),
+                        widget::toggler(self.config.use_gh_token)
+                            .on_toggle(Message::SetUseGhToken),
+                    ));
+                if let Some(days) = self.pat_expiry_days() {
+                    let warning = if days > 0 {
+                        fl!("token-expires-in", days = days)
+                    } else {
+                        fl!("token-expired")
+                    };
+                    let color = BadgeTier::Warn.color(self.uses_high_contrast());
+                    section = section.add(widget::text::body(warning).class(color));
+                }
+                if !self.config.use_gh_token {
+                    section = section
+                        .add(
+                            widget::text_input("ghp_...", &self.pat_input)
+                                .on_input(Message::SetPatInput),
+                        )
+                        .add(
+                            widget::row()
+                                .push(widget::horizontal_space())
+                                .push(
+                                    widget::button::suggested(fl!("save"))
+                                        .on_press(Message::SavePat),
+                                ),
+                        )
+                        .add(widget::text::body(fl!("token-command-label")))
+                        .add(
+                            widget::text_input(
+                                "pass show github/token",
+                                &self.token_command_input,
+                            )
+                            .on_input(Message::SetTokenCommandInput),
+                        )
+                        .add(widget::text::caption(fl!("token-command-hint")))
+                        .add(
+                            widget::row()
+                                .push(widget::horizontal_space())
+                                .push(
+                                    widget::button::suggested(fl!("save"))
+                                        .on_press(Message::SaveTokenCommand),
+                                ),
+                        );
+                }
+                section.into()
+            }
         };
 
+        // No preset is selected while a custom query is in use.
+        let selected_preset = QUERY_PRESET_VALUES
+            .iter()
+            .position(|&query| query == self.config.search_query);
+
+        let mut query_section = widget::settings::section()
+            .title(fl!("search-query-label"))
+            .add(widget::settings::item(
+                fl!("query-preset-label"),
+                widget::dropdown(QUERY_PRESET_LABELS, selected_preset, Message::SetQueryPreset),
+            ))
+            .add(
+                widget::text_input(SEARCH_QUERY, &self.query_input)
+                    .on_input(Message::SetQueryInput),
+            )
+            .add(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(widget::button::suggested(fl!("save")).on_press(Message::SaveQuery)),
+            )
+            .add(widget::settings::item(
+                fl!("exclude-own-label"),
+                widget::toggler(self.config.exclude_own).on_toggle(Message::SetExcludeOwn),
+            ))
+            .add(widget::settings::item(
+                fl!("include-approved-label"),
+                widget::toggler(self.config.include_approved)
+                    .on_toggle(Message::SetIncludeApproved),
+            ))
+            .add(widget::text::body(fl!("org-filter-label")))
+            .add(
+                widget::text_input(fl!("org-filter-placeholder"), &self.org_input)
+                    .on_input(Message::SetOrgInput),
+            );
+        if self.org_input_invalid {
+            query_section = query_section.add(widget::text::caption(fl!("org-filter-invalid")));
+        }
+        let query_section: Element<_> = query_section
+            .add(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(widget::button::suggested(fl!("save")).on_press(Message::SaveOrg)),
+            )
+            .add(widget::text::body(fl!("comparison-label")))
+            .add(
+                widget::text_input(
+                    "is:pr reviewed-by:@me updated:>={today}",
+                    &self.comparison_input,
+                )
+                .on_input(Message::SetComparisonInput),
+            )
+            .add(widget::text::caption(fl!("comparison-hint")))
+            .add(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(
+                        widget::button::suggested(fl!("save"))
+                            .on_press(Message::SaveComparisonQuery),
+                    ),
+            )
+            .into();
+
         let selected_interval =
             POLL_VALUES.iter().position(|&v| v == self.config.poll_interval_secs);
 
+        let selected_foreground_interval = FOREGROUND_POLL_VALUES
+            .iter()
+            .position(|&v| v == self.config.foreground_interval_secs);
+
+        let selected_min_age = MIN_AGE_VALUES.iter().position(|&v| v == self.config.min_age_hours);
+
+        let selected_visibility =
+            VISIBILITY_VALUES.iter().position(|&v| v == self.config.repo_visibility);
+
+        let selected_list_limit =
+            LIST_LIMIT_VALUES.iter().position(|&v| v == self.config.pr_list_limit);
+
+        let selected_sort = SORT_VALUES.iter().position(|&s| s == self.config.pr_sort);
+
+        let selected_time_display =
+            TIME_DISPLAY_VALUES.iter().position(|&t| t == self.config.time_display);
+        let selected_count_click =
+            COUNT_CLICK_VALUES.iter().position(|&a| a == self.config.count_click);
+
+        let selected_palette =
+            PALETTE_VALUES.iter().position(|&p| p == self.config.badge_palette);
+
+        let selected_quiet_start =
+            QUIET_START_VALUES.iter().position(|&s| s == self.config.quiet_start_secs);
+
+        let selected_dim_after =
+            DIM_AFTER_VALUES.iter().position(|&n| n == self.config.dim_after_failures);
+
+        let selected_stale_after =
+            STALE_AFTER_VALUES.iter().position(|&s| s == self.config.stale_after_secs);
+
+        let selected_auto_open =
+            AUTO_OPEN_VALUES.iter().position(|&n| n == self.config.auto_open_threshold);
+
         let general_section: Element<_> = widget::settings::section()
             .title(fl!("general-label"))
             .add(widget::settings::item(
                 fl!("poll-interval-label"),
                 widget::dropdown(POLL_LABELS, selected_interval, Message::SetPollInterval),
             ))
+            .add(widget::settings::item(
+                fl!("foreground-interval-label"),
+                widget::dropdown(
+                    FOREGROUND_POLL_LABELS,
+                    selected_foreground_interval,
+                    Message::SetForegroundInterval,
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("min-age-label"),
+                widget::dropdown(MIN_AGE_LABELS, selected_min_age, Message::SetMinAge),
+            ))
+            .add(widget::settings::item(
+                fl!("repo-visibility-label"),
+                widget::dropdown(
+                    VISIBILITY_LABELS,
+                    selected_visibility,
+                    Message::SetRepoVisibility,
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("pr-sort-label"),
+                widget::dropdown(SORT_LABELS, selected_sort, Message::SetPrSort),
+            ))
+            .add(widget::settings::item(
+                fl!("pr-list-limit-label"),
+                widget::dropdown(LIST_LIMIT_LABELS, selected_list_limit, Message::SetPrListLimit),
+            ))
+            .add(widget::settings::item(
+                fl!("time-display-label"),
+                widget::dropdown(
+                    TIME_DISPLAY_LABELS,
+                    selected_time_display,
+                    Message::SetTimeDisplay,
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("count-click-label"),
+                widget::dropdown(COUNT_CLICK_LABELS, selected_count_click, Message::SetCountClick),
+            ))
+            .add(widget::settings::item(
+                fl!("accurate-count-label"),
+                widget::toggler(self.config.accurate_count).on_toggle(Message::SetAccurateCount),
+            ))
+            .add(widget::settings::item(
+                fl!("track-changes-requested"),
+                widget::toggler(self.config.track_changes_requested)
+                    .on_toggle(Message::SetTrackChangesRequested),
+            ))
+            .add(widget::settings::item(
+                fl!("track-conflicts"),
+                widget::toggler(self.config.track_conflicts).on_toggle(Message::SetTrackConflicts),
+            ))
+            .add(widget::settings::item(
+                fl!("notifications-label"),
+                widget::toggler(self.config.notifications).on_toggle(Message::SetNotifications),
+            ))
+            .add(widget::settings::item(
+                fl!("auto-open-label"),
+                widget::dropdown(
+                    AUTO_OPEN_LABELS,
+                    selected_auto_open,
+                    Message::SetAutoOpenThreshold,
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("quiet-start-label"),
+                widget::dropdown(QUIET_START_LABELS, selected_quiet_start, Message::SetQuietStart),
+            ))
+            .add(widget::settings::item(
+                fl!("daily-progress-label"),
+                widget::toggler(self.config.show_daily_progress)
+                    .on_toggle(Message::SetShowDailyProgress),
+            ))
+            .add(widget::settings::item(
+                fl!("count-history-label"),
+                widget::toggler(self.config.show_count_history)
+                    .on_toggle(Message::SetShowCountHistory),
+            ))
+            .add(widget::settings::item(
+                fl!("show-icon-label"),
+                widget::toggler(self.config.show_icon).on_toggle(Message::SetShowIcon),
+            ))
+            .add(widget::text::body(fl!("label-format-label")))
+            .add(
+                widget::text_input("{count}", &self.label_format_input)
+                    .on_input(Message::SetLabelFormatInput),
+            )
+            .add(widget::text::caption(fl!("label-format-hint")))
+            .add(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(
+                        widget::button::suggested(fl!("save"))
+                            .on_press(Message::SaveLabelFormat),
+                    ),
+            )
+            .add(widget::settings::item(
+                fl!("autohide-label"),
+                widget::toggler(self.config.autohide).on_toggle(Message::SetAutohide),
+            ))
+            .add(widget::text::body(fl!("outputs-label")))
+            .add(
+                widget::text_input(fl!("outputs-placeholder"), &self.outputs_input)
+                    .on_input(Message::SetOutputsInput),
+            )
+            .add(widget::text::caption(fl!(
+                "outputs-hint",
+                output = self.core.applet.output_name.clone()
+            )))
+            .add(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(widget::button::suggested(fl!("save")).on_press(Message::SaveOutputs)),
+            )
+            .add(widget::settings::item(
+                fl!("dim-after-failures-label"),
+                widget::dropdown(
+                    DIM_AFTER_LABELS,
+                    selected_dim_after,
+                    Message::SetDimAfterFailures,
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("stale-after-label"),
+                widget::dropdown(STALE_AFTER_LABELS, selected_stale_after, Message::SetStaleAfter),
+            ))
+            .add(widget::settings::item(
+                fl!("dim-during-dnd-label"),
+                widget::toggler(self.config.dim_during_dnd).on_toggle(Message::SetDimDuringDnd),
+            ))
+            .add(widget::settings::item(
+                fl!("badge-palette-label"),
+                widget::dropdown(PALETTE_LABELS, selected_palette, Message::SetBadgePalette),
+            ))
+            .add(widget::settings::item(
+                fl!("animate-count-label"),
+                widget::toggler(self.config.animate_count).on_toggle(Message::SetAnimateCount),
+            ))
+            .add(widget::settings::item(
+                fl!("badge-accent-label"),
+                widget::toggler(self.config.badge_accent).on_toggle(Message::SetBadgeAccent),
+            ))
+            .add(widget::settings::item(
+                fl!("badge-errors-label"),
+                widget::toggler(self.config.badge_errors).on_toggle(Message::SetBadgeErrors),
+            ))
+            .add(widget::settings::item(
+                fl!("badge-error-dot-label"),
+                widget::toggler(self.config.badge_error_dot)
+                    .on_toggle(Message::SetBadgeErrorDot),
+            ))
             .into();
 
+        // In the order of SETTINGS_SECTION_LABELS, which the section list jumps to.
+        let sections: [Element<_>; SETTINGS_SECTION_LABELS.len()] = [
+            widget::column().push(auth_section).push(method_section).spacing(8).into(),
+            query_section,
+            general_section,
+            self.working_hours_section(),
+            self.extra_metrics_section(),
+            self.project_section(),
+            self.tabs_section(),
+            self.row_fields_section(),
+            self.badge_colors_section(),
+            self.advanced_section(),
+            self.diagnostics_section(),
+            self.reset_section(),
+        ];
+
+        // Buttons, so the list is reachable with Tab and a section opens with Enter.
+        let nav = SETTINGS_SECTION_LABELS.iter().enumerate().fold(
+            widget::column(),
+            |column, (idx, label)| {
+                column.push(
+                    widget::button::text(*label)
+                        .selected(self.settings_section == Some(idx))
+                        .width(cosmic::iced::Length::Fill)
+                        .on_press(Message::SelectSettingsSection(idx)),
+                )
+            },
+        );
+
         widget::column()
             .push(
                 widget::container(header)
                     .padding([8, 16]),
             )
             .push(
-                widget::column()
-                    .push(auth_section)
-                    .push(method_section)
-                    .push(general_section)
+                widget::row()
+                    .push(
+                        widget::scrollable(nav.spacing(2).padding([0, 4, 12, 8]))
+                            .width(cosmic::iced::Length::Fixed(SETTINGS_NAV_WIDTH)),
+                    )
+                    .push(
+                        widget::scrollable(
+                            widget::container(
+                                sections
+                                    .into_iter()
+                                    .zip(SETTINGS_SECTION_IDS.iter())
+                                    .fold(widget::column(), |column, (element, id)| {
+                                        column.push(widget::container(element).id(id.clone()))
+                                    })
+                                    .spacing(8)
+                                    .padding([0, 12, 12, 4]),
+                            )
+                            .padding([0, SCROLLBAR_GUTTER, 0, 0]),
+                        )
+                        .id(SETTINGS_SCROLL_ID.clone()),
+                    ),
+            )
+            .into()
+    }
+
+    /// Schedule outside of which nothing is polled and no notifications are sent.
+    fn working_hours_section(&self) -> Element<'_, Message> {
+        let mut section = widget::settings::section()
+            .title(fl!("working-hours-label"))
+            .add(widget::settings::item(
+                fl!("working-hours-enabled"),
+                widget::toggler(self.config.working_hours_enabled)
+                    .on_toggle(Message::SetWorkingHoursEnabled),
+            ));
+        if !self.config.working_hours_enabled {
+            return section.into();
+        }
+
+        let days = WORK_DAYS.iter().fold(widget::row().spacing(4), |row, &(day, label)| {
+            row.push(
+                widget::checkbox(label, self.config.work_days.contains(&day))
+                    .on_toggle(move |enabled| Message::ToggleWorkDay(day, enabled)),
+            )
+        });
+        section = section
+            .add(widget::settings::item(
+                fl!("work-start-label"),
+                widget::dropdown(
+                    HOUR_LABELS,
+                    Some(self.config.work_start_hour as usize),
+                    Message::SetWorkStart,
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("work-end-label"),
+                widget::dropdown(
+                    HOUR_LABELS,
+                    Some(self.config.work_end_hour as usize),
+                    Message::SetWorkEnd,
+                ),
+            ))
+            .add(days);
+        section.into()
+    }
+
+    /// Up to [`MAX_EXTRA_METRICS`] extra searches shown as their own badges on the panel.
+    fn extra_metrics_section(&self) -> Element<'_, Message> {
+        let mut section = widget::settings::section().title(fl!("extra-metrics-label"));
+        // A slot is offered once the ones before it are filled.
+        let slots = (self.config.extra_metrics.len() + 1).min(MAX_EXTRA_METRICS);
+        for slot in 0..slots {
+            // Hand-edited queries that match no preset leave the dropdown unselected.
+            let selected = match self.config.extra_metrics.get(slot) {
+                None => Some(0),
+                Some(query) => QUERY_PRESET_VALUES
+                    .iter()
+                    .position(|preset| preset == query)
+                    .map(|idx| idx + 1),
+            };
+            section = section.add(widget::settings::item(
+                fl!("extra-metric-label", number = slot + 1),
+                widget::dropdown(EXTRA_METRIC_LABELS, selected, move |idx| {
+                    Message::SetExtraMetric(slot, idx)
+                }),
+            ));
+        }
+        section
+            .add(widget::settings::item(
+                fl!("badge-aggregate-label"),
+                widget::toggler(self.config.badge_aggregate).on_toggle(Message::SetBadgeAggregate),
+            ))
+            .into()
+    }
+
+    /// Projects (v2) board whose items assigned to me are counted in the popup.
+    fn project_section(&self) -> Element<'_, Message> {
+        widget::settings::section()
+            .title(fl!("project-label"))
+            .add(
+                widget::text_input(
+                    "https://github.com/orgs/acme/projects/1",
+                    &self.project_input,
+                )
+                .on_input(Message::SetProjectInput),
+            )
+            .add(widget::text::caption(fl!("project-hint")))
+            .add(widget::text::body(fl!("project-ignored-status-label")))
+            .add(
+                widget::text_input("Done", &self.project_status_input)
+                    .on_input(Message::SetProjectStatusInput),
+            )
+            .add(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(widget::button::suggested(fl!("save")).on_press(Message::SaveProject)),
+            )
+            .into()
+    }
+
+    /// Popup tabs: the existing ones with a remove button, and inputs for a new one.
+    fn tabs_section(&self) -> Element<'_, Message> {
+        let mut section = widget::settings::section().title(fl!("tabs-label"));
+        for (idx, tab) in self.config.tabs.iter().enumerate() {
+            section = section.add(widget::settings::item(
+                tab.name.clone(),
+                widget::row()
+                    .push(widget::text::caption(tab.query.clone()))
+                    .push(widget::button::text(fl!("remove")).on_press(Message::RemoveTab(idx)))
                     .spacing(8)
-                    .padding([0, 12, 12, 12]),
+                    .align_y(Alignment::Center),
+            ));
+        }
+        section
+            .add(
+                widget::text_input(fl!("tab-name-placeholder"), &self.tab_name_input)
+                    .on_input(Message::SetTabNameInput),
+            )
+            .add(
+                widget::text_input(SEARCH_QUERY, &self.tab_query_input)
+                    .on_input(Message::SetTabQueryInput),
             )
+            .add(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(widget::button::suggested(fl!("add-tab")).on_press(Message::AddTab)),
+            )
+            .add(widget::settings::item(
+                fl!("badge-sums-tabs-label"),
+                widget::toggler(self.config.badge_sums_tabs).on_toggle(Message::SetBadgeSumsTabs),
+            ))
             .into()
     }
+
+    /// Which parts of each PR list row are shown.
+    fn row_fields_section(&self) -> Element<'_, Message> {
+        let mut section = widget::settings::section().title(fl!("row-fields-label"));
+        for &field in ROW_FIELDS {
+            section = section.add(widget::settings::item(
+                field.label(),
+                widget::toggler(field.enabled(&self.config))
+                    .on_toggle(move |enabled| Message::SetRowField(field, enabled)),
+            ));
+        }
+        section.into()
+    }
+
+    /// Advanced: a custom `#rrggbb` colour per badge tier. Invalid values are flagged and
+    /// the built-in colour is used instead.
+    fn badge_colors_section(&self) -> Element<'_, Message> {
+        let mut section = widget::settings::section().title(fl!("badge-colors-label"));
+        for &tier in COLOR_TIERS {
+            let value = self
+                .badge_color_inputs
+                .get(&tier)
+                .map_or_else(|| tier.custom_color(&self.config), String::as_str);
+            let valid = value.trim().is_empty() || parse_hex_color(value).is_some();
+            let label = if valid {
+                tier.label()
+            } else {
+                fl!("badge-color-invalid", tier = tier.label())
+            };
+            let save = (valid && self.badge_color_inputs.contains_key(&tier))
+                .then_some(Message::SaveBadgeColor(tier));
+            section = section.add(widget::settings::item(
+                label,
+                widget::row()
+                    .push(
+                        widget::text_input("#rrggbb", value)
+                            .on_input(move |hex| Message::SetBadgeColorInput(tier, hex))
+                            .width(100),
+                    )
+                    .push(widget::button::text(fl!("save")).on_press_maybe(save))
+                    .spacing(4)
+                    .align_y(Alignment::Center),
+            ));
+        }
+        section.into()
+    }
+
+    /// Advanced: the REST API version sent with every request, the realtime relay, and TLS
+    /// options for GitHub Enterprise instances with internal certificates.
+    fn advanced_section(&self) -> Element<'_, Message> {
+        widget::settings::section()
+            .title(fl!("advanced-label"))
+            .add(widget::text::body(fl!("api-version-label")))
+            .add(
+                widget::text_input(github::DEFAULT_API_VERSION, &self.api_version_input)
+                    .on_input(Message::SetApiVersionInput),
+            )
+            .add(widget::text::caption(fl!("api-version-hint")))
+            .add(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(
+                        widget::button::suggested(fl!("save")).on_press(Message::SaveApiVersion),
+                    ),
+            )
+            .add(widget::text::body(fl!("realtime-url-label")))
+            .add(
+                widget::text_input("https://relay.example.com/events", &self.realtime_url_input)
+                    .on_input(Message::SetRealtimeUrlInput),
+            )
+            .add(widget::text::caption(fl!("realtime-url-hint")))
+            .add(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(
+                        widget::button::suggested(fl!("save"))
+                            .on_press(Message::SaveRealtimeUrl),
+                    ),
+            )
+            .add(widget::text::body(fl!("custom-ca-label")))
+            .add(
+                widget::text_input("/path/to/ca.pem", &self.ca_path_input)
+                    .on_input(Message::SetCaPathInput),
+            )
+            .add(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(widget::button::suggested(fl!("save")).on_press(Message::SaveCaPath)),
+            )
+            .add(widget::settings::item(
+                fl!("http1-only-label"),
+                widget::toggler(self.config.http1_only).on_toggle(Message::SetHttp1Only),
+            ))
+            .add(widget::settings::item(
+                fl!("no-connection-reuse-label"),
+                widget::toggler(self.config.no_connection_reuse)
+                    .on_toggle(Message::SetNoConnectionReuse),
+            ))
+            .add(widget::settings::item(
+                fl!("insecure-tls-label"),
+                widget::toggler(self.config.allow_insecure_tls)
+                    .on_toggle(Message::SetAllowInsecureTls),
+            ))
+            .add(widget::text::caption(fl!("insecure-tls-warning")))
+            .into()
+    }
+
+    /// Timing and status of recent fetches.
+    fn diagnostics_section(&self) -> Element<'_, Message> {
+        let format_ms = |duration: Duration| format!("{} ms", duration.as_millis());
+
+        let last = self
+            .last_fetch_stats
+            .map_or_else(|| "—".to_string(), |stats| format_ms(stats.elapsed));
+        let average = if self.fetch_durations.is_empty() {
+            "—".to_string()
+        } else {
+            let total: Duration = self.fetch_durations.iter().sum();
+            format_ms(total / self.fetch_durations.len() as u32)
+        };
+        let http_status = self
+            .last_fetch_stats
+            .and_then(|stats| stats.http_status)
+            .map_or_else(|| "—".to_string(), |status| status.to_string());
+        let reset_peak = (self.state.all_time_peak > 0).then_some(Message::ResetPeak);
+
+        let mut section = widget::settings::section()
+            .title(fl!("diagnostics-label"))
+            .add(widget::settings::item(fl!("last-fetch-duration"), widget::text(last)))
+            .add(widget::settings::item(
+                fl!("average-fetch-duration", count = self.fetch_durations.len()),
+                widget::text(average),
+            ))
+            .add(widget::settings::item(fl!("last-http-status"), widget::text(http_status)))
+            .add(widget::settings::item(
+                fl!("all-time-peak"),
+                widget::row()
+                    .push(widget::text(format_count(self.state.all_time_peak)))
+                    .push(widget::button::text(fl!("reset-peak")).on_press_maybe(reset_peak))
+                    .spacing(8)
+                    .align_y(Alignment::Center),
+            ));
+        if self.config.auth_method == AuthMethod::Pat {
+            section = section.add(widget::settings::item(
+                fl!("curl-command"),
+                widget::button::standard(fl!("copy-curl-command"))
+                    .on_press(Message::CopyCurlCommand),
+            ));
+        }
+        section.add(self.test_fetch_view()).into()
+    }
+
+    /// Button to run a real fetch with the current settings, and everything it reported.
+    fn test_fetch_view(&self) -> Element<'_, Message> {
+        let button = if self.test_fetch_running {
+            widget::button::standard(fl!("loading"))
+        } else {
+            widget::button::standard(fl!("run-test-fetch")).on_press(Message::RunTestFetch)
+        };
+        let mut column = widget::column()
+            .push(widget::row().push(widget::horizontal_space()).push(button))
+            .spacing(4);
+
+        let Some((description, result)) = &self.test_fetch else {
+            return column.into();
+        };
+
+        let (outcome, stats, details) = match result {
+            Ok(results) => (
+                fl!("test-fetch-count", count = format_count(results.total_count)),
+                results.stats,
+                None,
+            ),
+            Err(err) => (
+                format!("{}: {err}", err.category()),
+                err.stats,
+                err.details.clone(),
+            ),
+        };
+        let http_status = stats
+            .http_status
+            .map_or_else(|| "—".to_string(), |status| status.to_string());
+
+        column = column
+            .push(widget::text::caption(description.clone()))
+            .push(widget::text::body(outcome))
+            .push(widget::text::caption(fl!(
+                "test-fetch-stats",
+                status = http_status,
+                ms = stats.elapsed.as_millis().to_string()
+            )));
+        if let Some(details) = details {
+            column = column.push(wrapped_text(details, 12));
+        }
+        column.into()
+    }
+
+    /// Diagnostics, cache, config file and reset-to-defaults buttons; reset expands
+    /// into a confirmation.
+    fn reset_section(&self) -> Element<'_, Message> {
+        if !self.confirm_reset {
+            let config_buttons = widget::row()
+                .push(
+                    widget::button::standard(fl!("open-config"))
+                        .on_press(Message::OpenConfigDir),
+                )
+                .push(
+                    widget::button::standard(fl!("reload-config"))
+                        .on_press(Message::ReloadConfig),
+                )
+                .spacing(8);
+            let other_buttons = widget::row()
+                .push(
+                    widget::button::standard(fl!("copy-diagnostics"))
+                        .on_press(Message::CopyDiagnostics),
+                )
+                .push(widget::button::standard(fl!("clear-cache")).on_press(Message::ClearCache))
+                .push(widget::horizontal_space())
+                .push(
+                    widget::button::destructive(fl!("reset-defaults"))
+                        .on_press(Message::RequestReset),
+                )
+                .spacing(8);
+            return widget::column()
+                .push(config_buttons)
+                .push(other_buttons)
+                .spacing(8)
+                .into();
+        }
+
+        widget::settings::section()
+            .add(widget::text::body(fl!("reset-confirm")))
+            .add(
+                widget::checkbox(fl!("reset-keep-pat"), self.reset_keep_pat)
+                    .on_toggle(Message::SetResetKeepPat),
+            )
+            .add(
+                widget::row()
+                    .push(widget::horizontal_space())
+                    .push(widget::button::standard(fl!("cancel")).on_press(Message::CancelReset))
+                    .push(
+                        widget::button::destructive(fl!("reset"))
+                            .on_press(Message::ConfirmReset),
+                    )
+                    .spacing(8),
+            )
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn badge(
+        error: Option<&FetchError>,
+        count: Option<u64>,
+        config: &Config,
+    ) -> (String, BadgeTier) {
+        compute_badge(error, count, false, config).expect("badge shown")
+    }
+
+    #[test]
+    fn badge_tiers_change_at_their_boundaries() {
+        let config = Config::default();
+        for (count, tier) in [
+            (0, BadgeTier::Zero),
+            (1, BadgeTier::Normal),
+            (5, BadgeTier::Normal),
+            (6, BadgeTier::Warn),
+            (10, BadgeTier::Warn),
+            (11, BadgeTier::Critical),
+        ] {
+            assert_eq!(badge(None, Some(count), &config), (count.to_string(), tier));
+        }
+    }
+
+    #[test]
+    fn badge_caps_large_counts() {
+        let config = Config::default();
+        assert_eq!(badge(None, Some(999), &config).0, "999");
+        assert_eq!(badge(None, Some(1000), &config), ("999+".into(), BadgeTier::Critical));
+    }
+
+    #[test]
+    fn badge_error_takes_priority_over_count() {
+        let config = Config::default();
+        let err = FetchError::new(ErrorKind::Network, "offline");
+        assert_eq!(badge(Some(&err), Some(3), &config), ("!".into(), BadgeTier::Error));
+        assert_eq!(badge(Some(&err), None, &config), ("!".into(), BadgeTier::Error));
+    }
+
+    #[test]
+    fn badge_keeps_count_when_errors_are_off_or_shown_as_dot() {
+        let err = FetchError::new(ErrorKind::Network, "offline");
+        let quiet = Config {
+            badge_errors: false,
+            ..Config::default()
+        };
+        assert_eq!(badge(Some(&err), Some(3), &quiet), ("3".into(), BadgeTier::Normal));
+        let dot = Config {
+            badge_error_dot: true,
+            ..Config::default()
+        };
+        assert_eq!(badge(Some(&err), Some(3), &dot), ("3".into(), BadgeTier::Normal));
+        assert!(shows_error_dot(Some(&err), Some(3), &dot));
+    }
+
+    #[test]
+    fn badge_is_hidden_without_count_or_error() {
+        assert_eq!(compute_badge(None, None, false, &Config::default()), None);
+    }
+
+    #[test]
+    fn stale_badge_shows_unknown() {
+        let config = Config::default();
+        assert_eq!(
+            compute_badge(None, Some(0), true, &config),
+            Some(("?".into(), BadgeTier::Unknown))
+        );
+    }
+
+    #[test]
+    fn relative_time_after_clock_stepped_back_reads_just_now() {
+        let now = chrono::Utc::now();
+        for ahead in [chrono::TimeDelta::minutes(10), chrono::TimeDelta::days(3)] {
+            assert_eq!(format_time(now + ahead, TimeDisplay::Relative), fl!("time-just-now"));
+        }
+    }
+
+    #[test]
+    fn relative_time_counts_recent_past() {
+        let now = chrono::Utc::now();
+        assert_eq!(
+            format_time(now - chrono::TimeDelta::minutes(5), TimeDisplay::Relative),
+            fl!("time-minutes-ago", count = 5_i64)
+        );
+        assert_eq!(
+            format_time(now - chrono::TimeDelta::hours(3), TimeDisplay::Relative),
+            fl!("time-hours-ago", count = 3_i64)
+        );
+    }
+
+    #[test]
+    fn relative_time_after_clock_jumped_ahead_falls_back_to_clock_time() {
+        let long_ago = chrono::Utc::now() - chrono::TimeDelta::days(MAX_RELATIVE_DAYS + 1);
+        assert_eq!(
+            format_time(long_ago, TimeDisplay::Relative),
+            format_time(long_ago, TimeDisplay::LocalClock)
+        );
+    }
+
+    #[test]
+    fn utc_time_on_another_day_includes_the_date() {
+        let time = chrono::DateTime::parse_from_rfc3339("2020-01-02T03:04:05Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(
+            format_time(time, TimeDisplay::Utc),
+            format!("{} UTC", format_date_time(&time, *DATE_LOCALE))
+        );
+    }
+
+    #[test]
+    fn dates_follow_the_locale() {
+        let time = chrono::DateTime::parse_from_rfc3339("2020-01-02T03:04:05Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(format_date_time(&time, None), "2020-01-02 03:04");
+        assert_eq!(format_date_time(&time, Some(chrono::Locale::de_DE)), "02.01.2020 03:04");
+        assert_eq!(format_date_time(&time, Some(chrono::Locale::en_US)), "01/02/2020 03:04");
+    }
+
+    #[test]
+    fn terminal_search_passes_query_words_separately() {
+        assert_eq!(
+            terminal_gh_args(
+                TerminalGhCommand::SearchPrs,
+                "is:pr  is:open review-requested:@me",
+                Some("owner/name"),
+            ),
+            ["search", "prs", "--", "is:pr", "is:open", "review-requested:@me"]
+        );
+    }
+
+    #[test]
+    fn terminal_pr_status_uses_the_filtered_repository() {
+        assert_eq!(
+            terminal_gh_args(TerminalGhCommand::PrStatus, "is:pr", Some("owner/name")),
+            ["pr", "status", "--repo", "owner/name"]
+        );
+        assert_eq!(terminal_gh_args(TerminalGhCommand::PrStatus, "is:pr", None), ["pr", "status"]);
+    }
+
+    #[test]
+    fn settings_section_list_selects_known_sections() {
+        let mut app = AppModel::default();
+        let _ = app.update(Message::SelectSettingsSection(2));
+        assert_eq!(app.settings_section, Some(2));
+        let _ = app.update(Message::SelectSettingsSection(SETTINGS_SECTION_LABELS.len()));
+        assert_eq!(app.settings_section, Some(2));
+        assert_eq!(SETTINGS_SECTION_IDS.len(), SETTINGS_SECTION_LABELS.len());
+    }
+
+    #[test]
+    fn release_versions_compare_numerically() {
+        assert!(is_newer_version("1.1.0", ""));
+        assert!(is_newer_version("1.10.0", "1.9.2"));
+        assert!(!is_newer_version("1.1.0", "1.1.0"));
+        assert!(!is_newer_version("1.0.9", "1.1.0"));
+    }
 }
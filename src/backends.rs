@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: GPL-3.0
+
+//! Auth backends registered at startup, for the `auth_backend` setting to pick from.
+//!
+//! Every executable in a backend directory becomes a backend named after its file:
+//! with `auth_backend = "vault"`, PAT mode runs `<dir>/vault` and uses the token it prints.
+//! Packagers add a credential provider by installing a helper there, without patching the
+//! applet. The directories are `/usr/lib/<app id>/backends` and then
+//! `$XDG_DATA_HOME/<app id>/backends`, so a user's helper replaces a system one of the same
+//! name. Backends written in Rust are registered in [`register_all`] as well.
+
+use crate::app::AppModel;
+use crate::github::{self, ErrorKind, FetchError, GithubBackend};
+use cosmic::Application;
+use futures_util::future::BoxFuture;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// Registers every backend this install provides. Call before anything fetches.
+pub fn register_all() {
+    for dir in backend_dirs() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if is_executable(&path) {
+                github::register_backend(ExecutableBackend { name: name.to_string(), path });
+            }
+        }
+    }
+}
+
+/// Directories searched for backend executables, lowest precedence first.
+fn backend_dirs() -> Vec<PathBuf> {
+    let system = Path::new("/usr/lib").join(AppModel::APP_ID).join("backends");
+    let user = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .map(|base| base.join(AppModel::APP_ID).join("backends"));
+    std::iter::once(system).chain(user).collect()
+}
+
+fn is_executable(path: &Path) -> bool {
+    std::fs::metadata(path)
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+/// A helper program that prints the token on stdout.
+struct ExecutableBackend {
+    name: String,
+    path: PathBuf,
+}
+
+impl GithubBackend for ExecutableBackend {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn token(&self) -> BoxFuture<'_, Result<String, FetchError>> {
+        Box::pin(async move {
+            let output = tokio::process::Command::new(&self.path).output().await.map_err(|e| {
+                FetchError::new(
+                    ErrorKind::Network,
+                    format!("{} could not be run: {e}", self.path.display()),
+                )
+            })?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(FetchError::new(
+                    ErrorKind::Unauthorized,
+                    format!("The \"{}\" auth backend failed.", self.name),
+                )
+                .with_details(&stderr));
+            }
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        })
+    }
+}
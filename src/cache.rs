@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: GPL-3.0
+
+//! Files cached under `$XDG_CACHE_HOME/<app id>`, kept below a size cap by evicting the
+//! least recently used ones. Everything cached can be rebuilt, so callers may ignore
+//! failures.
+
+use crate::app::AppModel;
+use cosmic::Application;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Total size the cache directory is trimmed to after every write.
+const MAX_CACHE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// The applet's cache directory, following the XDG base directory spec.
+fn cache_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+    Some(base.join(AppModel::APP_ID))
+}
+
+/// Reads a cached file and marks it as recently used.
+pub fn read(key: &str) -> Option<Vec<u8>> {
+    let path = cache_dir()?.join(key);
+    let data = fs::read(&path).ok()?;
+    // The modification time doubles as the last-use time for eviction.
+    if let Ok(file) = File::options().write(true).open(&path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+    Some(data)
+}
+
+/// Writes a cached file, then evicts the least recently used files over the size cap.
+pub fn write(key: &str, data: &[u8]) -> io::Result<()> {
+    let dir = cache_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no cache directory"))?;
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join(key), data)?;
+    evict(&dir, MAX_CACHE_BYTES)
+}
+
+/// Removes everything the applet has cached.
+pub fn clear() -> io::Result<()> {
+    match cache_dir() {
+        Some(dir) if dir.exists() => fs::remove_dir_all(dir),
+        _ => Ok(()),
+    }
+}
+
+/// Deletes files, oldest use first, until the directory holds at most `max_bytes`.
+fn evict(dir: &Path, max_bytes: u64) -> io::Result<()> {
+    let mut files: Vec<(SystemTime, u64, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if !metadata.is_file() {
+                return None;
+            }
+            Some((metadata.modified().ok()?, metadata.len(), entry.path()))
+        })
+        .collect();
+    files.sort_by_key(|(used, _, _)| *used);
+
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    for (_, len, path) in files {
+        if total <= max_bytes {
+            break;
+        }
+        fs::remove_file(path)?;
+        total -= len;
+    }
+    Ok(())
+}
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: GPL-3.0
+
+//! Headless command-line modes that reuse the applet's fetch logic without the GUI.
+
+use crate::app::{self, AppModel};
+use crate::config::{AuthMethod, Config, State};
+use crate::diagnostics;
+use crate::github;
+use cosmic::Application;
+
+/// Async runtime for the modes that talk to GitHub.
+fn runtime() -> Option<tokio::runtime::Runtime> {
+    tokio::runtime::Runtime::new()
+        .map_err(|why| eprintln!("failed to start async runtime: {why}"))
+        .ok()
+}
+
+/// The config the applet would run with: the managed one if present, else the user's.
+fn effective_config() -> Config {
+    Config::load_managed().unwrap_or_else(|| Config::load(AppModel::APP_ID).1)
+}
+
+/// Runs a single fetch with the saved config and prints the result, either as plain text
+/// or as `{"count": N}` / `{"error": "..."}`. Returns the process exit code.
+pub fn once(json: bool) -> i32 {
+    let request = app::review_search(&effective_config());
+    let Some(runtime) = runtime() else {
+        return 1;
+    };
+
+    match runtime.block_on(github::fetch_pr_count(&request)) {
+        Ok(results) => {
+            if json {
+                println!("{}", serde_json::json!({ "count": results.total_count }));
+            } else {
+                println!("{}", results.total_count);
+            }
+            0
+        }
+        Err(err) => {
+            if json {
+                println!("{}", serde_json::json!({ "error": err.to_string() }));
+            } else {
+                eprintln!("{err}");
+            }
+            1
+        }
+    }
+}
+
+/// Checks the saved setup step by step (API URL, credentials, a real search), printing a
+/// report. Stops at the first failure; returns 0 only if everything works.
+pub fn check() -> i32 {
+    let config = effective_config();
+    print!("{}", diagnostics::issue_report(&config, None));
+    println!();
+
+    let Some(runtime) = runtime() else {
+        return 1;
+    };
+    let pass = |step: &str| println!("ok      {step}");
+    let fail = |step: &str| {
+        println!("FAILED  {step}");
+        1
+    };
+
+    if let Err(why) = url::Url::parse(github::API_BASE_URL) {
+        return fail(&format!("API base URL {}: {why}", github::API_BASE_URL));
+    }
+    pass("API base URL");
+
+    match config.auth_method {
+        AuthMethod::GhCli => match runtime.block_on(github::check_gh_status()) {
+            Ok(account) => pass(&format!("gh is logged in ({account})")),
+            Err(why) => return fail(&format!("gh: {why}")),
+        },
+        AuthMethod::Pat if !config.auth_backend.is_empty() => {
+            pass(&format!("PAT mode, using the \"{}\" auth backend", config.auth_backend))
+        }
+        AuthMethod::Pat if config.use_gh_token => pass("PAT mode, using gh's token"),
+        AuthMethod::Pat if !config.token_command.is_empty() => {
+            pass("PAT mode, using the token command")
+        }
+        AuthMethod::Pat if config.github_pat.is_empty() => return fail("no PAT configured"),
+        AuthMethod::Pat => pass("PAT configured"),
+    }
+
+    match runtime.block_on(github::fetch_pr_count(&app::review_search(&config))) {
+        Ok(results) => pass(&format!("search works ({} PRs)", results.total_count)),
+        Err(err) => return fail(&format!("search: {err}")),
+    }
+    0
+}
+
+/// Prints the running applet's last known status as one short line for external status
+/// bars: `GH:<count>`, `GH:!` after a failed fetch, or `GH:?` before the first one. Reads
+/// the persisted state only, so it is cheap to call often and never touches the network.
+pub fn status() -> i32 {
+    let (_, state) = State::load(AppModel::APP_ID);
+    println!("{}", status_line(&state));
+    0
+}
+
+fn status_line(state: &State) -> String {
+    match (state.last_fetch_failed, state.cached_pr_count) {
+        (true, _) => "GH:!".to_string(),
+        (false, Some(count)) => format!("GH:{count}"),
+        (false, None) => "GH:?".to_string(),
+    }
+}
@@ -1,7 +1,12 @@
 // SPDX-License-Identifier: GPL-3.0
 
+use crate::github::{DEFAULT_API_VERSION, SEARCH_QUERY};
+use chrono::{DateTime, Datelike, Local, Timelike, Utc, Weekday};
 use cosmic::cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, CosmicConfigEntry};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash)]
 pub enum AuthMethod {
@@ -10,20 +15,505 @@ pub enum AuthMethod {
     Pat,
 }
 
-#[derive(Debug, Clone, CosmicConfigEntry, Eq, PartialEq)]
+/// Colour palette used for the panel badge.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub enum BadgePalette {
+    /// Use the high-contrast palette when the COSMIC theme has high contrast enabled.
+    #[default]
+    Auto,
+    Standard,
+    HighContrast,
+}
+
+/// Order of the PR list in the popup.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub enum PrSort {
+    /// GitHub's best-match relevance order.
+    #[default]
+    Default,
+    Newest,
+    Oldest,
+    Repo,
+}
+
+/// How timestamps are shown in the popup.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub enum TimeDisplay {
+    /// "5 min ago".
+    #[default]
+    Relative,
+    LocalClock,
+    Utc,
+}
+
+/// What clicking the count in the popup does.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub enum CountClickAction {
+    /// Open the review queue search on GitHub.
+    #[default]
+    OpenGitHub,
+    OpenOldest,
+    Nothing,
+}
+
+/// What the terminal opened from the popup runs.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub enum TerminalGhCommand {
+    /// `gh search prs` for the review query.
+    #[default]
+    SearchPrs,
+    /// `gh pr status`, for the filtered repository or the terminal's working directory.
+    PrStatus,
+}
+
+/// Which repositories' PRs are counted.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub enum RepoVisibility {
+    #[default]
+    All,
+    PublicOnly,
+    PrivateOnly,
+}
+
+/// A named search with a tab of its own in the popup.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct PopupTab {
+    pub name: String,
+    pub query: String,
+}
+
+/// System-wide config that, when present, replaces the user's and locks the settings. JSON
+/// with the same field names as [`Config`]; missing fields take their defaults.
+pub const MANAGED_CONFIG_PATH: &str = "/etc/cosmic-github-applet/config";
+
+#[derive(Debug, Clone, CosmicConfigEntry, Deserialize, Eq, PartialEq)]
+#[serde(default)]
 #[version = 2]
 pub struct Config {
+    /// Whether the account is polled at all; lets users pause it without losing settings.
+    pub account_enabled: bool,
     pub auth_method: AuthMethod,
     pub github_pat: String,
+    /// In PAT mode, use the token from `gh auth token` instead of `github_pat`.
+    pub use_gh_token: bool,
+    /// In PAT mode, run this shell command and use what it prints as the token, e.g. to
+    /// read it from a secret store (empty = use `github_pat`).
+    pub token_command: String,
+    /// In PAT mode, take the token from the auth backend installed under this name (empty =
+    /// none). Only settable in the config file.
+    pub auth_backend: String,
+    /// GitHub REST API version to request (empty = the server's default).
+    pub api_version: String,
+    /// Accept invalid TLS certificates on the PAT path. Dangerous; for self-signed
+    /// GitHub Enterprise instances only.
+    pub allow_insecure_tls: bool,
+    /// Force HTTP/1.1 on the PAT path, for proxies that break HTTP/2.
+    pub http1_only: bool,
+    /// Don't keep connections open between requests on the PAT path.
+    pub no_connection_reuse: bool,
+    /// Extra trusted root certificate (PEM) for the PAT path.
+    pub custom_ca_path: Option<PathBuf>,
+    /// Server-Sent Events endpoint of a relay announcing review queue changes (empty = off).
+    pub realtime_url: String,
+    /// Poll interval while the popup is closed.
     pub poll_interval_secs: u64,
+    /// GitHub search behind the badge; defaults to PRs waiting for my review.
+    pub search_query: String,
+    /// Leave my own PRs out of the main search (`-author:@me`).
+    pub exclude_own: bool,
+    /// Keep approved PRs in the main search by dropping its `-review:approved` term.
+    pub include_approved: bool,
+    /// Only count PRs in this organization; added to every search as `org:<name>`.
+    pub org_filter: Option<String>,
+    /// Second search whose count is shown against the main one in the popup, e.g. reviews
+    /// done today (empty = off). `{today}` stands for the local date.
+    pub comparison_query: String,
+    /// Further searches counted on the panel as badges of their own.
+    pub extra_metrics: Vec<String>,
+    /// Extra popup tabs, each listing the PRs of its own search.
+    pub tabs: Vec<PopupTab>,
+    /// Add the tabs' counts to the main badge.
+    pub badge_sums_tabs: bool,
+    /// Show the sum of the main search and the extra metrics on the main badge instead of
+    /// separate badges.
+    pub badge_aggregate: bool,
+    /// Poll interval while the popup is open (0 = same as `poll_interval_secs`).
+    pub foreground_interval_secs: u64,
+    /// Terminal launcher used by "Open in terminal"; `gh` is appended as the command to run.
+    pub terminal_command: String,
+    /// The `gh` command "Open in terminal" runs.
+    pub terminal_gh_command: TerminalGhCommand,
+    /// Also poll PRs where I requested changes and am waiting on the author.
+    pub track_changes_requested: bool,
+    /// Also count my own open PRs that have merge conflicts.
+    pub track_conflicts: bool,
+    /// Projects (v2) board whose items assigned to me are counted: its URL or node ID
+    /// (empty = off).
+    pub project: String,
+    /// Items of `project` with this Status aren't counted (empty = count all).
+    pub project_ignored_status: String,
+    /// Show the GitHub icon on the panel; when off, only the count is shown.
+    pub show_icon: bool,
+    /// Panel text when the icon is hidden; `{count}` is replaced with the count.
+    pub label_format: String,
+    /// Take no panel space while every count is zero and nothing failed.
+    pub autohide: bool,
+    /// Outputs (e.g. `DP-1`) whose panels show the applet; empty = all of them. Every
+    /// panel runs its own instance, so the others just take no space.
+    pub outputs: Vec<String>,
+    pub badge_palette: BadgePalette,
+    /// Use the system accent colour for the normal (1–5 PRs) badge.
+    pub badge_accent: bool,
+    /// Briefly light up the badge when the count changes, unless animations are off.
+    pub animate_count: bool,
+    /// Show fetch errors as a "!" badge; when off, errors only appear in the popup.
+    pub badge_errors: bool,
+    /// With a known count, mark errors with a dot next to the count instead of a "!".
+    pub badge_error_dot: bool,
+    /// Dim the panel icon after this many failed fetches in a row (0 = never).
+    pub dim_after_failures: u32,
+    /// Show the count as unknown once the last successful fetch is older than this (0 = never).
+    pub stale_after_secs: u64,
+    /// Dim the panel icon while COSMIC's Do Not Disturb is on.
+    pub dim_during_dnd: bool,
+    /// Open the review queue in the browser when the count rises to this many PRs.
+    pub auto_open_threshold: Option<u32>,
+    /// Custom badge colours per tier as `#rrggbb`; empty or invalid keeps the built-in one.
+    pub badge_color_zero: String,
+    pub badge_color_normal: String,
+    pub badge_color_warn: String,
+    pub badge_color_critical: String,
+    pub badge_color_error: String,
+    pub badge_color_unknown: String,
+    /// Only count PRs opened at least this many hours ago (0 = count all).
+    pub min_age_hours: u32,
+    pub pr_sort: PrSort,
+    pub repo_visibility: RepoVisibility,
+    /// Count PRs by paging through the search results instead of trusting GitHub's
+    /// reported total; slower and uses more API calls.
+    pub accurate_count: bool,
+    /// Number of PRs listed in the popup.
+    pub pr_list_limit: usize,
+    pub time_display: TimeDisplay,
+    pub count_click: CountClickAction,
+    /// Parts of each PR list row; the PR number is shown either way.
+    pub row_show_title: bool,
+    pub row_show_repo: bool,
+    pub row_show_author: bool,
+    pub row_show_age: bool,
+    pub row_show_review_state: bool,
+    /// Link to the checks of PRs whose CI is failing.
+    pub row_show_ci: bool,
+    /// Show progress from the day's peak count towards an empty review queue.
+    pub show_daily_progress: bool,
+    /// Show a sparkline of recent counts in the popup.
+    pub show_count_history: bool,
+    /// Send a desktop notification when the count goes up.
+    pub notifications: bool,
+    /// Seconds after startup during which count changes aren't notified.
+    pub quiet_start_secs: u64,
+    /// Only poll and notify during working hours.
+    pub working_hours_enabled: bool,
+    pub work_days: Vec<Weekday>,
+    /// Local hour the working day starts, 0–23.
+    pub work_start_hour: u32,
+    /// Local hour the working day ends; before the start hour for overnight shifts.
+    pub work_end_hour: u32,
+    /// Applet version whose "What's new" notes were last dismissed.
+    pub last_seen_version: String,
+}
+
+impl Config {
+    /// Loads the persisted config along with the handle used to write it back. Falls back
+    /// to defaults if the config service is unavailable; `reopen` then keeps trying.
+    pub fn load(app_id: &str) -> (Option<cosmic_config::Config>, Self) {
+        let handler = cosmic_config::Config::new(app_id, Self::VERSION).ok();
+        let config = handler.as_ref().map(Self::read).unwrap_or_default();
+        (handler, config)
+    }
+
+    /// Retries opening the config after `load` failed, backing off between attempts: right
+    /// after login the config directory may not be ready yet, and without a handle nothing
+    /// the user changes would be saved.
+    pub async fn reopen(app_id: String) -> Option<cosmic_config::Config> {
+        let mut delay = CONFIG_RETRY_DELAY;
+        for attempt in 2..=CONFIG_ATTEMPTS {
+            tokio::time::sleep(delay).await;
+            match cosmic_config::Config::new(&app_id, Self::VERSION) {
+                Ok(handler) => return Some(handler),
+                Err(why) if attempt == CONFIG_ATTEMPTS => {
+                    eprintln!("config unavailable, settings won't be saved: {why}");
+                }
+                Err(_) => delay *= 2,
+            }
+        }
+        None
+    }
+
+    /// Reads the config from disk through an existing handle.
+    pub fn read(handler: &cosmic_config::Config) -> Self {
+        // Fields that fail to load fall back to their defaults; the rest are kept.
+        let mut config = match Self::get_entry(handler) {
+            Ok(config) => config,
+            Err((errors, config)) => {
+                log_load_errors("config", &errors);
+                config
+            }
+        };
+        config.migrate();
+        config
+    }
+
+    /// Fixes up values older versions could leave behind.
+    fn migrate(&mut self) {
+        // Old configs may have poll_interval_secs = 0 (u64 default).
+        if self.poll_interval_secs == 0 {
+            self.poll_interval_secs = 60;
+        }
+        if self.search_query.trim().is_empty() {
+            self.search_query = SEARCH_QUERY.to_string();
+        }
+    }
+
+    /// Directory cosmic-config keeps this applet's settings in, one file per field.
+    pub fn dir(app_id: &str) -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(base.join("cosmic").join(app_id).join(format!("v{}", Self::VERSION)))
+    }
+
+    /// Reads the administrator-managed config, if there is one. An unreadable file is
+    /// reported and ignored so the applet keeps working with the user's settings.
+    pub fn load_managed() -> Option<Self> {
+        let data = std::fs::read(MANAGED_CONFIG_PATH).ok()?;
+        match serde_json::from_slice::<Self>(&data) {
+            Ok(mut config) => {
+                config.migrate();
+                Some(config)
+            }
+            Err(why) => {
+                eprintln!("ignoring managed config {MANAGED_CONFIG_PATH}: {why}");
+                None
+            }
+        }
+    }
+
+    /// Whether `now` falls within the working hours, or the schedule is off. An overnight
+    /// shift belongs to the day it starts on.
+    pub fn is_working_time(&self, now: DateTime<Local>) -> bool {
+        if !self.working_hours_enabled {
+            return true;
+        }
+
+        let hour = now.hour();
+        let (start, end) = (self.work_start_hour, self.work_end_hour);
+        let shift_day = if start <= end {
+            (start..end).contains(&hour).then(|| now.weekday())
+        } else if hour >= start {
+            Some(now.weekday())
+        } else if hour < end {
+            Some(now.weekday().pred())
+        } else {
+            None
+        };
+        shift_day.is_some_and(|day| self.work_days.contains(&day))
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
+            account_enabled: true,
             auth_method: AuthMethod::GhCli,
             github_pat: String::new(),
+            use_gh_token: false,
+            token_command: String::new(),
+            auth_backend: String::new(),
+            api_version: DEFAULT_API_VERSION.to_string(),
+            allow_insecure_tls: false,
+            http1_only: false,
+            no_connection_reuse: false,
+            custom_ca_path: None,
+            realtime_url: String::new(),
             poll_interval_secs: 60,
+            search_query: SEARCH_QUERY.to_string(),
+            exclude_own: false,
+            include_approved: false,
+            org_filter: None,
+            comparison_query: String::new(),
+            extra_metrics: Vec::new(),
+            badge_aggregate: false,
+            tabs: Vec::new(),
+            badge_sums_tabs: false,
+            foreground_interval_secs: 0,
+            terminal_command: "cosmic-term -e".to_string(),
+            terminal_gh_command: TerminalGhCommand::SearchPrs,
+            track_changes_requested: false,
+            track_conflicts: false,
+            project: String::new(),
+            project_ignored_status: "Done".to_string(),
+            show_icon: true,
+            label_format: "{count}".to_string(),
+            autohide: false,
+            outputs: Vec::new(),
+            badge_palette: BadgePalette::Auto,
+            badge_accent: false,
+            animate_count: false,
+            badge_errors: true,
+            badge_error_dot: false,
+            dim_after_failures: 0,
+            dim_during_dnd: false,
+            stale_after_secs: 0,
+            auto_open_threshold: None,
+            badge_color_zero: String::new(),
+            badge_color_normal: String::new(),
+            badge_color_warn: String::new(),
+            badge_color_critical: String::new(),
+            badge_color_error: String::new(),
+            badge_color_unknown: String::new(),
+            min_age_hours: 0,
+            pr_sort: PrSort::Default,
+            repo_visibility: RepoVisibility::All,
+            accurate_count: false,
+            pr_list_limit: 10,
+            time_display: TimeDisplay::Relative,
+            count_click: CountClickAction::OpenGitHub,
+            row_show_title: true,
+            row_show_repo: false,
+            row_show_author: false,
+            row_show_age: true,
+            row_show_review_state: true,
+            row_show_ci: true,
+            show_daily_progress: false,
+            show_count_history: false,
+            notifications: false,
+            quiet_start_secs: 60,
+            working_hours_enabled: false,
+            work_days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            work_start_hour: 9,
+            work_end_hour: 17,
+            last_seen_version: String::new(),
         }
     }
 }
+
+/// Attempts at opening the config at startup before running with defaults.
+const CONFIG_ATTEMPTS: u32 = 4;
+
+/// Wait before the second attempt; doubled after each further failure.
+const CONFIG_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Runtime state persisted between runs, kept apart from the user's settings.
+#[derive(Debug, Default, Clone, CosmicConfigEntry, Eq, PartialEq)]
+#[version = 1]
+pub struct State {
+    /// Last successfully fetched PR count, shown until the first live fetch completes.
+    pub cached_pr_count: Option<u64>,
+    /// Highest count seen on `daily_peak_date`, the starting point of the daily progress.
+    pub daily_peak: u64,
+    /// Local date (`YYYY-MM-DD`) that `daily_peak` belongs to.
+    pub daily_peak_date: String,
+    /// Highest count ever seen, until reset from the settings.
+    pub all_time_peak: u64,
+    /// Whether the most recent main fetch failed, for `--status`.
+    pub last_fetch_failed: bool,
+    /// IDs of the listed PRs that have been shown in the popup.
+    pub seen_pr_ids: BTreeSet<u64>,
+    /// When the PAT expires, as last reported by GitHub; None for tokens without expiry.
+    pub pat_expires_at: Option<DateTime<Utc>>,
+}
+
+impl State {
+    /// Loads the persisted state along with the handle used to write it back.
+    pub fn load(app_id: &str) -> (Option<cosmic_config::Config>, Self) {
+        let handler = cosmic_config::Config::new_state(app_id, Self::VERSION).ok();
+        let state = handler
+            .as_ref()
+            .map(|h| match Self::get_entry(h) {
+                Ok(state) => state,
+                Err((errors, state)) => {
+                    log_load_errors("state", &errors);
+                    state
+                }
+            })
+            .unwrap_or_default();
+
+        (handler, state)
+    }
+}
+
+/// The part of COSMIC's notification settings the applet follows.
+#[derive(Debug, Default, Clone, CosmicConfigEntry, Eq, PartialEq)]
+#[version = 1]
+pub struct NotificationsConfig {
+    /// Do Not Disturb is on.
+    pub do_not_disturb: bool,
+}
+
+impl NotificationsConfig {
+    /// Config ID of COSMIC's notification daemon.
+    pub const ID: &'static str = "com.system76.CosmicNotifications";
+
+    /// Reads the current settings; without a readable config, DND counts as off.
+    pub fn load() -> Self {
+        cosmic_config::Config::new(Self::ID, Self::VERSION)
+            .ok()
+            .and_then(|handler| Self::get_entry(&handler).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Reports the entries that failed to load. Entries that are merely missing, such as
+/// fields added since the config was last written, are expected and skipped.
+fn log_load_errors(kind: &str, errors: &[cosmic_config::Error]) {
+    for message in load_error_messages(kind, errors) {
+        eprintln!("{message}");
+    }
+}
+
+/// The lines [`log_load_errors`] prints.
+fn load_error_messages(kind: &str, errors: &[cosmic_config::Error]) -> Vec<String> {
+    errors
+        .iter()
+        .filter(|why| why.is_err())
+        .map(|why| format!("failed to load {kind} entry, using its default: {why}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmic::cosmic_config::ConfigSet;
+
+    #[test]
+    fn corrupt_entry_is_logged_and_the_rest_still_loads() {
+        let dir = std::env::temp_dir()
+            .join(format!("cosmic-applet-github-status-test-{}", std::process::id()));
+        let handler =
+            cosmic_config::Config::with_custom_path("test.Config", Config::VERSION, dir.clone())
+                .expect("config handle");
+        handler.set("poll_interval_secs", 120_u64).unwrap();
+        // A string where a bool belongs.
+        handler.set("show_icon", "sometimes").unwrap();
+
+        let errors = match Config::get_entry(&handler) {
+            Ok(_) => Vec::new(),
+            Err((errors, _)) => errors,
+        };
+        let messages = load_error_messages("config", &errors);
+        let config = Config::read(&handler);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(messages.len(), 1, "{messages:?}");
+        assert_eq!(config.poll_interval_secs, 120);
+        assert!(config.show_icon);
+    }
+}
@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: GPL-3.0
+
+//! Environment details for bug reports. Nothing here may include credentials.
+
+use crate::config::{AuthMethod, Config};
+use crate::github::{self, FetchError};
+
+/// Markdown block describing the applet's setup, ready to paste into a GitHub issue.
+pub fn issue_report(config: &Config, last_error: Option<&FetchError>) -> String {
+    let auth_method = match config.auth_method {
+        AuthMethod::GhCli => "GitHub CLI",
+        AuthMethod::Pat => "Personal Access Token",
+    };
+
+    format!(
+        "### Environment\n\n\
+         - OS: {}\n\
+         - Applet version: {}\n\
+         - Auth method: {auth_method}\n\
+         - Poll interval: {} s\n\
+         - API base URL: {}\n\
+         - Last error: {}\n",
+        os_name(),
+        env!("CARGO_PKG_VERSION"),
+        config.poll_interval_secs,
+        redact_url(github::API_BASE_URL),
+        last_error.map_or("none", FetchError::category),
+    )
+}
+
+/// Distribution name from `/etc/os-release`.
+fn os_name() -> String {
+    std::fs::read_to_string("/etc/os-release")
+        .ok()
+        .and_then(|release| {
+            release.lines().find_map(|line| {
+                line.strip_prefix("PRETTY_NAME=")
+                    .map(|name| name.trim_matches('"').to_string())
+            })
+        })
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Strips user info and the query string, either of which may carry a token.
+fn redact_url(url: &str) -> String {
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    match url.split_once("://") {
+        Some((scheme, rest)) => {
+            let rest = rest.split_once('@').map_or(rest, |(_, host)| host);
+            format!("{scheme}://{rest}")
+        }
+        None => url.to_string(),
+    }
+}
@@ -0,0 +1,1479 @@
+// SPDX-License-Identifier: GPL-3.0
+
+//! GitHub search queries, run through either the `gh` CLI or an HTTP client with a PAT.
+//!
+//! The PAT path uses `reqwest` by default; building with `--no-default-features
+//! --features curl-backend` shells out to `curl` instead.
+
+#[cfg(not(any(feature = "reqwest-backend", feature = "curl-backend")))]
+compile_error!("enable either the `reqwest-backend` or the `curl-backend` feature");
+
+use crate::config::{AuthMethod, PrSort};
+use chrono::{DateTime, TimeDelta, Utc};
+use futures_util::future::BoxFuture;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// PRs where I am a requested reviewer and have not approved yet.
+pub const SEARCH_QUERY: &str = "is:pr is:open review-requested:@me -review:approved";
+
+/// PRs I reviewed with "changes requested" that are now waiting on their authors.
+pub const CHANGES_REQUESTED_QUERY: &str = "is:pr is:open reviewed-by:@me review:changes_requested";
+
+/// My open PRs, checked for merge conflicts.
+pub const AUTHORED_QUERY: &str = "is:pr is:open author:@me";
+
+/// Most PRs checked for merge conflicts; GraphQL search returns at most 100 per page.
+const MAX_CONFLICT_CHECKS: u32 = 100;
+
+/// Most node IDs GraphQL's `nodes(ids:)` accepts in one query.
+const MAX_NODE_IDS: usize = 100;
+
+/// Base URL of the GitHub REST API.
+pub const API_BASE_URL: &str = "https://api.github.com";
+
+/// REST API version requested by default.
+pub const DEFAULT_API_VERSION: &str = "2022-11-28";
+
+/// Shared HTTP client and the TLS settings it was built with, so connections to the API
+/// are reused between polls until those settings change.
+#[cfg(feature = "reqwest-backend")]
+static HTTP_CLIENT: Mutex<Option<(TlsOptions, reqwest::Client)>> = Mutex::new(None);
+
+/// Clock difference to GitHub, in minutes, beyond which failures are blamed on the local
+/// clock.
+#[cfg(feature = "reqwest-backend")]
+const MAX_CLOCK_SKEW_MINUTES: i64 = 5;
+
+/// GitHub's search rate limit for authenticated users. All searches share one token, so
+/// every page fetch waits for the limiter to stay under it.
+const SEARCH_REQUESTS_PER_MINUTE: f64 = 30.0;
+
+/// Token bucket shared by every search, however many queries are being polled.
+static SEARCH_LIMITER: LazyLock<Mutex<TokenBucket>> =
+    LazyLock::new(|| Mutex::new(TokenBucket::new(SEARCH_REQUESTS_PER_MINUTE)));
+
+/// Token read from `gh auth token`, the token command or an auth backend, keyed by where
+/// it came from and reused until a request is rejected with it.
+static BORROWED_TOKEN: Mutex<Option<(TokenSource, String)>> = Mutex::new(None);
+
+/// Where a borrowed token is read from.
+#[derive(Clone, PartialEq, Eq)]
+enum TokenSource {
+    Gh,
+    Command(String),
+    /// A [`GithubBackend`], by name.
+    Backend(String),
+}
+
+/// A custom source of the PAT, for installs that get credentials somewhere the applet
+/// doesn't know about (a Vault client, a keyring library). `backends::register_all` registers
+/// them before the applet starts; the `auth_backend` setting then selects one by name in PAT
+/// mode, ahead of gh's token and the token command.
+pub trait GithubBackend: Send + Sync {
+    /// Name the `auth_backend` setting refers to the backend by.
+    fn name(&self) -> &str;
+
+    /// Reads the token. The result is cached, and read again after GitHub rejects it.
+    fn token(&self) -> BoxFuture<'_, Result<String, FetchError>>;
+}
+
+/// Auth backends registered at startup.
+static BACKENDS: Mutex<Vec<Arc<dyn GithubBackend>>> = Mutex::new(Vec::new());
+
+/// Makes `backend` available to the `auth_backend` setting. A backend registered under an
+/// existing name replaces it.
+pub fn register_backend(backend: impl GithubBackend + 'static) {
+    let mut backends = BACKENDS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    backends.retain(|existing| existing.name() != backend.name());
+    backends.push(Arc::new(backend));
+}
+
+/// The registered backend called `name`, if there is one.
+fn backend(name: &str) -> Option<Arc<dyn GithubBackend>> {
+    let backends = BACKENDS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    backends.iter().find(|backend| backend.name() == name).cloned()
+}
+
+/// Largest page size the search API accepts.
+pub const MAX_PER_PAGE: u32 = 100;
+
+/// The search API never returns more than this many results, whatever `total_count` says.
+pub const SEARCH_RESULT_LIMIT: u32 = 1000;
+
+/// Connection settings for the PAT path, for GitHub Enterprise instances with internal
+/// certificates and for proxies that don't cope with the defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct TlsOptions {
+    /// Accept any certificate. Dangerous: anyone on the network can read the token.
+    pub allow_insecure: bool,
+    /// PEM file with an extra trusted root certificate.
+    pub custom_ca_path: Option<PathBuf>,
+    /// Speak HTTP/1.1 only instead of negotiating HTTP/2.
+    pub http1_only: bool,
+    /// Open a new connection for every request instead of reusing idle ones.
+    pub no_connection_reuse: bool,
+}
+
+/// Everything needed to run one search. Also used as the poller's subscription ID, so the
+/// poller restarts whenever any of these values changes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SearchRequest {
+    pub auth_method: AuthMethod,
+    pub pat: String,
+    /// Fetch the PAT from `gh auth token` instead of using `pat`.
+    pub use_gh_token: bool,
+    /// Shell command printing the PAT to use instead of `pat`, e.g. to read it from a
+    /// secret store (empty = none). `use_gh_token` takes precedence.
+    pub token_command: String,
+    /// Name of the registered [`GithubBackend`] supplying the PAT (empty = none). Takes
+    /// precedence over `use_gh_token` and `token_command`.
+    pub auth_backend: String,
+    /// Sent as `X-GitHub-Api-Version` to pin the REST API version (empty = server default).
+    pub api_version: String,
+    pub tls: TlsOptions,
+    pub query: String,
+    /// Number of items to collect alongside the total count, paging through results
+    /// as needed.
+    pub max_items: u32,
+    /// PRs created more recently than this are left out of the search (0 = keep all).
+    pub min_age_hours: u32,
+    pub sort: PrSort,
+    /// Count the items by paging through them instead of trusting `total_count`, up to
+    /// [`SEARCH_RESULT_LIMIT`]. Costs a request per page.
+    pub accurate_count: bool,
+}
+
+impl SearchRequest {
+    /// Whether the PAT comes from a command or an auth backend rather than from `pat`.
+    fn borrows_token(&self) -> bool {
+        matches!(self.auth_method, AuthMethod::Pat)
+            && (!self.auth_backend.is_empty()
+                || self.use_gh_token
+                || !self.token_command.is_empty())
+    }
+
+    /// The query sent to GitHub. A minimum age becomes a `created:<=` qualifier, so
+    /// GitHub's total count already leaves out PRs that are too young.
+    fn search_query(&self) -> String {
+        if self.min_age_hours == 0 {
+            return self.query.clone();
+        }
+        let cutoff = Utc::now() - TimeDelta::hours(i64::from(self.min_age_hours));
+        format!("{} created:<={}", self.query, cutoff.format("%Y-%m-%dT%H:%M:%SZ"))
+    }
+
+    /// `sort` and `order` parameters for the search API, so the fetched page already
+    /// holds the right PRs. Repository order has no API equivalent.
+    fn sort_params(&self) -> Option<(&'static str, &'static str)> {
+        match self.sort {
+            PrSort::Newest => Some(("created", "desc")),
+            PrSort::Oldest => Some(("created", "asc")),
+            PrSort::Default | PrSort::Repo => None,
+        }
+    }
+
+    /// Query parameters of one page of this search on the REST API.
+    fn search_params(&self, per_page: u32, page: u32) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("q", self.search_query()),
+            ("per_page", per_page.to_string()),
+            ("page", page.to_string()),
+        ];
+        if let Some((sort, order)) = self.sort_params() {
+            params.push(("sort", sort.to_string()));
+            params.push(("order", order.to_string()));
+        }
+        params
+    }
+
+    /// The first request this search makes in PAT mode as a curl command, with the same
+    /// URL, headers and connection options. The token is left to a `$GH_TOKEN` variable.
+    pub fn curl_command(&self) -> String {
+        let per_page = self.max_items.clamp(1, MAX_PER_PAGE);
+        let url = format!("{API_BASE_URL}/search/issues");
+        let mut command = format!("curl --get {}", shell_quote(&url));
+        for (name, value) in self.search_params(per_page, 1) {
+            let param = format!("{name}={value}");
+            command.push_str(&format!(" --data-urlencode {}", shell_quote(&param)));
+        }
+        command.push_str(" -H \"Authorization: Bearer $GH_TOKEN\"");
+        command.push_str(" -H 'Accept: application/vnd.github+json'");
+        if !self.api_version.is_empty() {
+            let header = format!("X-GitHub-Api-Version: {}", self.api_version);
+            command.push_str(&format!(" -H {}", shell_quote(&header)));
+        }
+        if self.tls.allow_insecure {
+            command.push_str(" --insecure");
+        }
+        if self.tls.http1_only {
+            command.push_str(" --http1.1");
+        }
+        if let Some(path) = &self.tls.custom_ca_path {
+            command.push_str(&format!(" --cacert {}", shell_quote(&path.to_string_lossy())));
+        }
+        command
+    }
+
+    /// The first request this search makes, as a command or URL for diagnostics. The
+    /// token is never included.
+    pub fn describe(&self) -> String {
+        match self.auth_method {
+            AuthMethod::GhCli => {
+                format!("gh api search/issues --method GET -f q='{}'", self.search_query())
+            }
+            AuthMethod::Pat => {
+                let encoded: String =
+                    url::form_urlencoded::byte_serialize(self.search_query().as_bytes()).collect();
+                format!("GET {API_BASE_URL}/search/issues?q={encoded}")
+            }
+        }
+    }
+}
+
+/// Quotes `text` as a single shell word.
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// A pull request as returned by the search API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullRequest {
+    /// Globally unique ID, unlike `number` which is per repository.
+    pub id: u64,
+    /// GraphQL node ID, used to look up the review decision.
+    pub node_id: String,
+    pub number: u64,
+    pub title: String,
+    pub html_url: String,
+    pub repository_url: String,
+    pub user: User,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct User {
+    pub login: String,
+}
+
+impl PullRequest {
+    /// Repository in `owner/name` form, derived from the API repository URL.
+    pub fn repo(&self) -> &str {
+        self.repository_url
+            .split("/repos/")
+            .nth(1)
+            .unwrap_or(&self.repository_url)
+    }
+
+    /// The PR's checks tab, listing its CI runs.
+    pub fn checks_url(&self) -> String {
+        format!("{}/checks", self.html_url)
+    }
+}
+
+/// Count and first page of items for a search.
+#[derive(Debug, Clone, Default)]
+pub struct SearchResults {
+    pub total_count: u64,
+    pub items: Vec<PullRequest>,
+    pub stats: FetchStats,
+    /// GitHub stopped the search early (usually a timeout), so the count may be too low.
+    pub incomplete: bool,
+}
+
+/// How the requests behind a fetch went, for diagnostics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FetchStats {
+    /// Time spent in requests, excluding waits for the rate limiter.
+    pub elapsed: Duration,
+    /// Status of the last HTTP response; only known for the reqwest backend.
+    pub http_status: Option<u16>,
+    /// When the PAT expires, as GitHub reports it for tokens with an expiry date; only
+    /// known for the reqwest backend.
+    pub token_expires_at: Option<DateTime<Utc>>,
+}
+
+impl SearchResults {
+    /// Orders the items client-side. Sorting is stable, so PRs within a repository keep
+    /// GitHub's relevance order.
+    fn sort(&mut self, sort: PrSort) {
+        match sort {
+            PrSort::Default => {}
+            PrSort::Newest => self.items.sort_by(|a, b| b.created_at.cmp(&a.created_at)),
+            PrSort::Oldest => self.items.sort_by(|a, b| a.created_at.cmp(&b.created_at)),
+            PrSort::Repo => self.items.sort_by(|a, b| a.repo().cmp(b.repo())),
+        }
+    }
+}
+
+/// Refills at `capacity` tokens per minute, allowing bursts of up to `capacity` requests.
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: f64) -> Self {
+        Self {
+            capacity,
+            tokens: capacity,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Takes a token, or returns how long to wait until one is available.
+    fn try_take(&mut self) -> Result<(), Duration> {
+        let now = Instant::now();
+        let refill_per_sec = self.capacity / 60.0;
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / refill_per_sec))
+        }
+    }
+}
+
+/// Waits until the shared search limiter allows another request.
+async fn wait_for_rate_limit() {
+    loop {
+        let wait = match SEARCH_LIMITER.lock() {
+            Ok(mut bucket) => bucket.try_take(),
+            // A poisoned lock only means another poll panicked; don't block fetching on it.
+            Err(_) => Ok(()),
+        };
+
+        match wait {
+            Ok(()) => return,
+            Err(delay) => tokio::time::sleep(delay).await,
+        }
+    }
+}
+
+/// Broad reason a fetch failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Missing or rejected credentials.
+    Unauthorized,
+    RateLimited,
+    /// GitHub's abuse detection (the "secondary rate limit") is blocking requests.
+    AbuseLimited,
+    Timeout,
+    /// The request never reached GitHub, or the tool to make it is missing.
+    Network,
+    /// GitHub answered with something we could not understand.
+    Parse,
+    /// The local clock is far enough off GitHub's that requests are rejected.
+    ClockSkew,
+    Other,
+}
+
+impl ErrorKind {
+    /// Parses a category name, case-insensitively.
+    fn from_category(name: &str) -> Option<Self> {
+        [
+            Self::Unauthorized,
+            Self::RateLimited,
+            Self::AbuseLimited,
+            Self::Timeout,
+            Self::Network,
+            Self::Parse,
+            Self::ClockSkew,
+            Self::Other,
+        ]
+        .into_iter()
+        .find(|kind| kind.category().eq_ignore_ascii_case(name))
+    }
+
+    /// Short, stable name of the error kind, safe to include in bug reports.
+    pub fn category(self) -> &'static str {
+        match self {
+            Self::Unauthorized => "Unauthorized",
+            Self::RateLimited => "RateLimited",
+            Self::AbuseLimited => "AbuseLimited",
+            Self::Timeout => "Timeout",
+            Self::Network => "Network",
+            Self::Parse => "Parse",
+            Self::ClockSkew => "ClockSkew",
+            Self::Other => "Other",
+        }
+    }
+}
+
+/// Wait after an abuse-detection error that came without a `Retry-After`.
+const DEFAULT_ABUSE_BACKOFF: Duration = Duration::from_secs(300);
+
+/// A search response before parsing.
+struct RawResponse {
+    body: String,
+    /// Only known for the reqwest backend.
+    http_status: Option<u16>,
+    retry_after: Option<Duration>,
+    token_expires_at: Option<DateTime<Utc>>,
+}
+
+/// Maximum length of the raw details kept with an error.
+const MAX_DETAILS_LEN: usize = 2000;
+
+/// A failed fetch: the message shown to the user, plus the raw response body or stderr
+/// when there is one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchError {
+    pub kind: ErrorKind,
+    message: String,
+    /// Raw output behind the error, redacted and truncated.
+    pub details: Option<String>,
+    pub stats: FetchStats,
+    /// How long GitHub asked us to wait (`Retry-After`), if it said.
+    pub retry_after: Option<Duration>,
+}
+
+impl FetchError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            details: None,
+            stats: FetchStats::default(),
+            retry_after: None,
+        }
+    }
+
+    /// Attaches raw output, truncated to a size the popup can show.
+    pub fn with_details(mut self, details: &str) -> Self {
+        let details = details.trim();
+        if !details.is_empty() {
+            let end = details
+                .char_indices()
+                .nth(MAX_DETAILS_LEN)
+                .map_or(details.len(), |(idx, _)| idx);
+            self.details = Some(details[..end].to_string());
+        }
+        self
+    }
+
+    /// Removes the PAT, and anything shaped like a GitHub token, from the message and
+    /// details.
+    fn redact(mut self, pat: &str) -> Self {
+        self.message = redact_tokens(&self.message, pat);
+        self.details = self.details.map(|details| redact_tokens(&details, pat));
+        self
+    }
+
+    /// Classifies an error message from `gh` or the API by its wording.
+    fn classify(message: String) -> Self {
+        let lower = message.to_lowercase();
+        // Secondary limits arrive as a 403, like a missing permission, so check first.
+        if lower.contains("secondary rate limit") || lower.contains("abuse") {
+            return Self::new(
+                ErrorKind::AbuseLimited,
+                "GitHub is temporarily blocking requests (secondary rate limit). Polling is \
+                 paused for a while; consider a longer poll interval.",
+            );
+        }
+
+        let kind = if lower.contains("rate limit") {
+            ErrorKind::RateLimited
+        } else if lower.contains("bad credentials")
+            || lower.contains("401")
+            || lower.contains("gh auth login")
+        {
+            ErrorKind::Unauthorized
+        } else if lower.contains("timeout") || lower.contains("timed out") {
+            ErrorKind::Timeout
+        } else if lower.contains("error connecting") || lower.contains("could not resolve") {
+            ErrorKind::Network
+        } else {
+            ErrorKind::Other
+        };
+        Self::new(kind, message)
+    }
+
+    #[cfg(feature = "reqwest-backend")]
+    fn from_reqwest(error: reqwest::Error) -> Self {
+        let kind = if error.is_timeout() {
+            ErrorKind::Timeout
+        } else if error.is_decode() {
+            ErrorKind::Parse
+        } else {
+            ErrorKind::Network
+        };
+        Self::new(kind, format!("Request failed: {error}"))
+    }
+
+    /// Short, stable name of the error kind, safe to include in bug reports.
+    pub fn category(&self) -> &'static str {
+        self.kind.category()
+    }
+
+    /// How long to hold off the next poll, for errors where polling on would make things
+    /// worse.
+    pub fn backoff(&self) -> Option<Duration> {
+        match self.kind {
+            ErrorKind::AbuseLimited => Some(self.retry_after.unwrap_or(DEFAULT_ABUSE_BACKOFF)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Replaces `pat` and any word starting with a GitHub token prefix with a placeholder.
+fn redact_tokens(text: &str, pat: &str) -> String {
+    const TOKEN_PREFIXES: &[&str] = &["ghp_", "gho_", "ghu_", "ghs_", "ghr_", "github_pat_"];
+
+    let text = if pat.is_empty() {
+        text.to_string()
+    } else {
+        text.replace(pat, "[REDACTED]")
+    };
+
+    text.split_inclusive(char::is_whitespace)
+        .map(|word| {
+            if TOKEN_PREFIXES.iter().any(|prefix| word.starts_with(prefix)) {
+                let trailing = &word[word.trim_end().len()..];
+                format!("[REDACTED]{trailing}")
+            } else {
+                word.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Raw search response; `message` is set instead of the results when the API errors.
+#[derive(Deserialize)]
+struct SearchResponse {
+    total_count: Option<u64>,
+    #[serde(default)]
+    incomplete_results: bool,
+    #[serde(default)]
+    items: Vec<PullRequest>,
+    message: Option<String>,
+}
+
+/// Environment variable that forces every fetch to fail with the named error kind, so each
+/// error presentation can be checked without reproducing the failure.
+const SIMULATE_ERROR_VAR: &str = "COSMIC_GITHUB_STATUS_SIMULATE_ERROR";
+
+pub async fn fetch_pr_count(request: &SearchRequest) -> Result<SearchResults, FetchError> {
+    if let Some(err) = simulated_error() {
+        return Err(err);
+    }
+
+    if request.borrows_token() {
+        return fetch_with_borrowed_token(request).await;
+    }
+
+    if matches!(request.auth_method, AuthMethod::Pat) && request.pat.is_empty() {
+        return Err(FetchError::new(
+            ErrorKind::Unauthorized,
+            "No PAT configured. Open Settings to add one.",
+        ));
+    }
+
+    fetch_all_pages(request)
+        .await
+        .map_err(|err| err.redact(&request.pat))
+}
+
+/// Runs the search over HTTP with a token from an auth backend, gh or the token command. A
+/// rejected token is read again once, in case it has been refreshed since it was cached.
+async fn fetch_with_borrowed_token(request: &SearchRequest) -> Result<SearchResults, FetchError> {
+    let mut request = SearchRequest {
+        pat: borrowed_token(request, false).await?,
+        ..request.clone()
+    };
+
+    let result = match fetch_all_pages(&request).await {
+        Err(err) if err.kind == ErrorKind::Unauthorized => {
+            request.pat = borrowed_token(&request, true).await?;
+            fetch_all_pages(&request).await
+        }
+        result => result,
+    };
+    result.map_err(|err| err.redact(&request.pat))
+}
+
+/// The token from the auth backend, the one gh is logged in with, or the one the token
+/// command prints, from the cache unless `refresh` is set.
+async fn borrowed_token(request: &SearchRequest, refresh: bool) -> Result<String, FetchError> {
+    let source = if !request.auth_backend.is_empty() {
+        TokenSource::Backend(request.auth_backend.clone())
+    } else if request.use_gh_token {
+        TokenSource::Gh
+    } else {
+        TokenSource::Command(request.token_command.clone())
+    };
+    if !refresh {
+        let cached = BORROWED_TOKEN.lock().ok().and_then(|cached| cached.clone());
+        if let Some((_, token)) = cached.filter(|(cached_source, _)| *cached_source == source) {
+            return Ok(token);
+        }
+    }
+
+    let (mut command, program, hint) = match &source {
+        TokenSource::Backend(name) => {
+            let backend = backend(name).ok_or_else(|| {
+                FetchError::new(
+                    ErrorKind::Unauthorized,
+                    format!("No auth backend named \"{name}\" is installed."),
+                )
+            })?;
+            let token = backend.token().await?.trim().to_string();
+            if token.is_empty() {
+                return Err(FetchError::new(
+                    ErrorKind::Unauthorized,
+                    format!("The \"{name}\" auth backend returned no token."),
+                ));
+            }
+            if let Ok(mut cached) = BORROWED_TOKEN.lock() {
+                *cached = Some((source, token.clone()));
+            }
+            return Ok(token);
+        }
+        TokenSource::Gh => {
+            let mut command = tokio::process::Command::new("gh");
+            command.args(["auth", "token"]);
+            (command, "gh", "Could not read gh's token. Run `gh auth login` first.")
+        }
+        TokenSource::Command(line) => {
+            let mut command = tokio::process::Command::new("sh");
+            command.args(["-c", line]);
+            (command, "sh", "The token command failed or printed nothing.")
+        }
+    };
+    let output = command
+        .output()
+        .await
+        .map_err(|e| FetchError::new(ErrorKind::Network, format!("{program} not found: {e}")))?;
+
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || token.is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(FetchError::new(ErrorKind::Unauthorized, hint).with_details(&stderr));
+    }
+
+    if let Ok(mut cached) = BORROWED_TOKEN.lock() {
+        *cached = Some((source, token.clone()));
+    }
+    Ok(token)
+}
+
+async fn fetch_all_pages(request: &SearchRequest) -> Result<SearchResults, FetchError> {
+    let max_items = request.max_items.max(1);
+    let per_page = max_items.min(MAX_PER_PAGE);
+    let last_page = max_items.div_ceil(per_page).min(SEARCH_RESULT_LIMIT / per_page);
+
+    let mut results = fetch_page(request, per_page, 1).await?;
+    for page in 2..=last_page {
+        let collected = u32::try_from(results.items.len()).unwrap_or(u32::MAX);
+        let exhausted = if request.accurate_count {
+            // Page until GitHub runs out of results, whatever total_count claims.
+            collected == 0 || collected % per_page != 0
+        } else {
+            u64::from(collected) >= results.total_count
+        };
+        if collected >= max_items || exhausted {
+            break;
+        }
+
+        let next = fetch_page(request, per_page, page).await?;
+        results.stats = FetchStats {
+            elapsed: results.stats.elapsed + next.stats.elapsed,
+            http_status: next.stats.http_status,
+            token_expires_at: next.stats.token_expires_at.or(results.stats.token_expires_at),
+        };
+        results.incomplete |= next.incomplete;
+        if next.items.is_empty() {
+            break;
+        }
+        results.items.extend(next.items);
+    }
+    results.items.truncate(max_items as usize);
+    if request.accurate_count {
+        results.total_count = results.items.len() as u64;
+    }
+
+    results.sort(request.sort);
+    Ok(results)
+}
+
+/// The error requested through [`SIMULATE_ERROR_VAR`], if any.
+fn simulated_error() -> Option<FetchError> {
+    let name = std::env::var(SIMULATE_ERROR_VAR).ok()?;
+    let kind = ErrorKind::from_category(name.trim())?;
+    Some(
+        FetchError::new(kind, format!("Simulated {} error", kind.category()))
+            .with_details(&format!("Set by {SIMULATE_ERROR_VAR}={name}")),
+    )
+}
+
+/// Fetches one page of search results.
+async fn fetch_page(
+    request: &SearchRequest,
+    per_page: u32,
+    page: u32,
+) -> Result<SearchResults, FetchError> {
+    wait_for_rate_limit().await;
+
+    let started = Instant::now();
+    let response = match request.auth_method {
+        AuthMethod::GhCli => fetch_via_gh_cli(request, per_page, page)
+            .await
+            .map(|body| RawResponse {
+                body,
+                http_status: None,
+                retry_after: None,
+                token_expires_at: None,
+            }),
+        AuthMethod::Pat => fetch_via_pat(request, per_page, page).await,
+    };
+    let mut stats = FetchStats {
+        elapsed: started.elapsed(),
+        http_status: None,
+        token_expires_at: None,
+    };
+
+    let response = response.map_err(|err| FetchError { stats, ..err })?;
+    stats.http_status = response.http_status;
+    stats.token_expires_at = response.token_expires_at;
+
+    parse_search_response(&response.body)
+        .map(|results| SearchResults { stats, ..results })
+        .map_err(|err| FetchError {
+            stats,
+            retry_after: response.retry_after,
+            ..err
+        })
+}
+
+async fn fetch_via_gh_cli(
+    request: &SearchRequest,
+    per_page: u32,
+    page: u32,
+) -> Result<String, FetchError> {
+    let mut command = tokio::process::Command::new("gh");
+    command.args([
+        "api",
+        "search/issues",
+        "--method", "GET",
+        "-f", &format!("q={}", request.search_query()),
+        "-f", &format!("per_page={per_page}"),
+        "-f", &format!("page={page}"),
+    ]);
+    if let Some((sort, order)) = request.sort_params() {
+        command.args(["-f", &format!("sort={sort}"), "-f", &format!("order={order}")]);
+    }
+    if !request.api_version.is_empty() {
+        command.args(["-H", &format!("X-GitHub-Api-Version: {}", request.api_version)]);
+    }
+
+    let output = command
+        .output()
+        .await
+        .map_err(|e| FetchError::new(ErrorKind::Network, format!("gh not found: {e}")))?;
+
+    if !output.status.success() {
+        // On HTTP errors gh prints the response body to stdout and a summary to stderr.
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(FetchError::classify(stderr.trim().to_string())
+            .with_details(&format!("{stdout}\n{stderr}")));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(feature = "reqwest-backend")]
+async fn fetch_via_pat(
+    request: &SearchRequest,
+    per_page: u32,
+    page: u32,
+) -> Result<RawResponse, FetchError> {
+    let mut http_request = http_client(&request.tls)?
+        .get(format!("{API_BASE_URL}/search/issues"))
+        .query(&request.search_params(per_page, page))
+        .bearer_auth(&request.pat)
+        .header("Accept", "application/vnd.github+json");
+    if !request.api_version.is_empty() {
+        http_request = http_request.header("X-GitHub-Api-Version", &request.api_version);
+    }
+
+    let response = http_request
+        .send()
+        .await
+        .map_err(FetchError::from_reqwest)?;
+
+    let status = response.status();
+    let skew = clock_skew(response.headers());
+    let token_expires_at = token_expiration(response.headers());
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+        .map(Duration::from_secs);
+    let body = response.text().await.map_err(FetchError::from_reqwest)?;
+
+    // A badly wrong local clock makes token validation fail with confusing errors;
+    // name the real cause instead.
+    if !status.is_success() {
+        let skew_minutes = skew.map(|skew| skew.num_minutes().abs());
+        if let Some(minutes) = skew_minutes.filter(|&minutes| minutes > MAX_CLOCK_SKEW_MINUTES) {
+            return Err(FetchError::new(
+                ErrorKind::ClockSkew,
+                format!("System clock appears incorrect (off by {minutes} minutes)"),
+            )
+            .with_details(&body));
+        }
+    }
+
+    // Error statuses still carry a JSON body with a `message`, handled by the parser.
+    Ok(RawResponse {
+        body,
+        http_status: Some(status.as_u16()),
+        retry_after,
+        token_expires_at,
+    })
+}
+
+/// The shared client for `tls`, rebuilt when the TLS settings have changed.
+#[cfg(feature = "reqwest-backend")]
+fn http_client(tls: &TlsOptions) -> Result<reqwest::Client, FetchError> {
+    let mut cached = HTTP_CLIENT.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((_, client)) = cached.as_ref().filter(|(options, _)| options == tls) {
+        return Ok(client.clone());
+    }
+
+    let mut builder = reqwest::Client::builder()
+        // GitHub rejects API requests without a User-Agent.
+        .user_agent(concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(30))
+        .danger_accept_invalid_certs(tls.allow_insecure);
+    if tls.http1_only {
+        builder = builder.http1_only();
+    }
+    if tls.no_connection_reuse {
+        builder = builder.pool_max_idle_per_host(0);
+    }
+    if let Some(path) = &tls.custom_ca_path {
+        let certificate = std::fs::read(path)
+            .ok()
+            .and_then(|pem| reqwest::Certificate::from_pem(&pem).ok())
+            .ok_or_else(|| {
+                FetchError::new(
+                    ErrorKind::Other,
+                    format!("Could not load CA certificate from {}", path.display()),
+                )
+            })?;
+        builder = builder.add_root_certificate(certificate);
+    }
+
+    let client = builder.build().map_err(FetchError::from_reqwest)?;
+    *cached = Some((tls.clone(), client.clone()));
+    Ok(client)
+}
+
+/// Difference between local time and the server's `Date` response header.
+#[cfg(feature = "reqwest-backend")]
+fn clock_skew(headers: &reqwest::header::HeaderMap) -> Option<TimeDelta> {
+    let date = headers.get(reqwest::header::DATE)?.to_str().ok()?;
+    let server_time = DateTime::parse_from_rfc2822(date).ok()?;
+    Some(Utc::now().signed_duration_since(server_time))
+}
+
+/// Expiry of the token used, from the header GitHub adds for PATs that expire. It looks
+/// like `2024-05-01 12:00:00 UTC`, or carries a numeric offset instead of `UTC`.
+#[cfg(feature = "reqwest-backend")]
+fn token_expiration(headers: &reqwest::header::HeaderMap) -> Option<DateTime<Utc>> {
+    let value = headers
+        .get("github-authentication-token-expiration")?
+        .to_str()
+        .ok()?
+        .trim();
+    if let Some(utc) = value.strip_suffix(" UTC") {
+        let naive = chrono::NaiveDateTime::parse_from_str(utc, "%Y-%m-%d %H:%M:%S").ok()?;
+        return Some(naive.and_utc());
+    }
+    DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S %z")
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+/// The HTTP status and headers aren't captured from curl.
+#[cfg(all(feature = "curl-backend", not(feature = "reqwest-backend")))]
+async fn fetch_via_pat(
+    request: &SearchRequest,
+    per_page: u32,
+    page: u32,
+) -> Result<RawResponse, FetchError> {
+    let mut command = tokio::process::Command::new("curl");
+    command.args([
+        "--silent",
+        "--show-error",
+        "--get",
+        "--data-urlencode", &format!("q={}", request.search_query()),
+        "--data", &format!("per_page={per_page}&page={page}"),
+        "-H", &format!("Authorization: Bearer {}", request.pat),
+        "-H", "Accept: application/vnd.github+json",
+    ]);
+    if let Some((sort, order)) = request.sort_params() {
+        command.args(["--data", &format!("sort={sort}&order={order}")]);
+    }
+    if !request.api_version.is_empty() {
+        command.args(["-H", &format!("X-GitHub-Api-Version: {}", request.api_version)]);
+    }
+    if request.tls.allow_insecure {
+        command.arg("--insecure");
+    }
+    // Each curl run is a connection of its own, so there is no reuse to turn off.
+    if request.tls.http1_only {
+        command.arg("--http1.1");
+    }
+    // Unlike reqwest, curl uses this file instead of the system bundle, not alongside it.
+    if let Some(path) = &request.tls.custom_ca_path {
+        command.arg("--cacert").arg(path);
+    }
+
+    let output = command
+        .arg(format!("{API_BASE_URL}/search/issues"))
+        .output()
+        .await
+        .map_err(|e| FetchError::new(ErrorKind::Network, format!("curl not found: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // curl exits with 28 when the operation timed out.
+        let kind = match output.status.code() {
+            Some(28) => ErrorKind::Timeout,
+            _ => ErrorKind::Network,
+        };
+        return Err(
+            FetchError::new(kind, format!("Request failed: {}", stderr.trim())).with_details(&stderr),
+        );
+    }
+
+    Ok(RawResponse {
+        body: String::from_utf8_lossy(&output.stdout).into_owned(),
+        http_status: None,
+        retry_after: None,
+        token_expires_at: None,
+    })
+}
+
+fn parse_search_response(body: &str) -> Result<SearchResults, FetchError> {
+    let response: SearchResponse = serde_json::from_str(body).map_err(|e| {
+        FetchError::new(ErrorKind::Parse, format!("JSON parse error: {e}")).with_details(body)
+    })?;
+
+    match (response.total_count, response.message) {
+        (Some(total_count), _) => Ok(SearchResults {
+            total_count,
+            items: response.items,
+            stats: FetchStats::default(),
+            incomplete: response.incomplete_results,
+        }),
+        (None, Some(message)) => {
+            Err(FetchError::classify(format!("API error: {message}")).with_details(body))
+        }
+        (None, None) => Err(
+            FetchError::new(ErrorKind::Parse, "total_count not found in response")
+                .with_details(body),
+        ),
+    }
+}
+
+/// Overall review decision of a PR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewState {
+    Approved,
+    ChangesRequested,
+    /// Reviews are still required, or the repository doesn't require any.
+    Pending,
+}
+
+/// What the list shows about a PR beyond the search results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrStatus {
+    pub review: ReviewState,
+    /// The combined status of the head commit's checks failed or errored.
+    pub ci_failing: bool,
+}
+
+#[derive(Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+    message: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NodesData {
+    nodes: Vec<Option<StatusNode>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StatusNode {
+    id: String,
+    review_decision: Option<String>,
+    commits: Option<CommitNodes>,
+}
+
+#[derive(Deserialize)]
+struct CommitNodes {
+    nodes: Vec<Option<CommitNode>>,
+}
+
+#[derive(Deserialize)]
+struct CommitNode {
+    commit: Commit,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Commit {
+    status_check_rollup: Option<StatusCheckRollup>,
+}
+
+#[derive(Deserialize)]
+struct StatusCheckRollup {
+    state: String,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct SearchData {
+    search: SearchNodes,
+}
+
+#[derive(Deserialize)]
+struct SearchNodes {
+    nodes: Vec<Option<MergeableNode>>,
+}
+
+#[derive(Deserialize)]
+struct MergeableNode {
+    mergeable: Option<String>,
+}
+
+/// Looks up the review decision and CI status of the given PRs, by node ID, in one GraphQL
+/// query per [`MAX_NODE_IDS`] PRs. CI status is the check rollup of each PR's latest commit.
+pub async fn fetch_pr_statuses(
+    request: &SearchRequest,
+    node_ids: &[String],
+) -> Result<HashMap<String, PrStatus>, FetchError> {
+    if node_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let query = "query($ids: [ID!]!) { nodes(ids: $ids) { \
+                 ... on PullRequest { id reviewDecision \
+                 commits(last: 1) { nodes { commit { statusCheckRollup { state } } } } } } }";
+    let mut nodes = Vec::with_capacity(node_ids.len());
+    for batch in node_ids.chunks(MAX_NODE_IDS) {
+        let data: NodesData =
+            graphql(request, query, &serde_json::json!({ "ids": batch })).await?;
+        nodes.extend(data.nodes);
+    }
+
+    Ok(nodes
+        .into_iter()
+        .flatten()
+        .map(|node| {
+            let review = match node.review_decision.as_deref() {
+                Some("APPROVED") => ReviewState::Approved,
+                Some("CHANGES_REQUESTED") => ReviewState::ChangesRequested,
+                _ => ReviewState::Pending,
+            };
+            let ci_failing = node
+                .commits
+                .and_then(|commits| commits.nodes.into_iter().flatten().next())
+                .and_then(|last| last.commit.status_check_rollup)
+                .is_some_and(|rollup| matches!(rollup.state.as_str(), "FAILURE" | "ERROR"));
+            (node.id, PrStatus { review, ci_failing })
+        })
+        .collect())
+}
+
+/// Counts the PRs matched by `request.query` that have merge conflicts. The REST search
+/// can't filter on mergeability, so this goes through GraphQL. GitHub computes mergeability
+/// lazily; PRs it hasn't worked out yet count as not conflicting until the next poll.
+pub async fn fetch_conflicting_count(request: &SearchRequest) -> Result<u64, FetchError> {
+    let query = format!(
+        "query($q: String!) {{ search(query: $q, type: ISSUE, first: {MAX_CONFLICT_CHECKS}) {{ \
+         nodes {{ ... on PullRequest {{ mergeable }} }} }} }}"
+    );
+    let data: SearchData =
+        graphql(request, &query, &serde_json::json!({ "q": request.search_query() })).await?;
+
+    let conflicting = data
+        .search
+        .nodes
+        .into_iter()
+        .flatten()
+        .filter(|node| node.mergeable.as_deref() == Some("CONFLICTING"))
+        .count();
+    Ok(conflicting as u64)
+}
+
+/// Fields of a Projects (v2) board needed to count its items: each item's status and the
+/// assignees of the issue, PR or draft behind it.
+const PROJECT_ITEM_FIELDS: &str = "items(first: 100) { nodes { \
+    status: fieldValueByName(name: \"Status\") { \
+        ... on ProjectV2ItemFieldSingleSelectValue { name } } \
+    content { \
+        ... on Issue { assignees(first: 10) { nodes { login } } } \
+        ... on PullRequest { assignees(first: 10) { nodes { login } } } \
+        ... on DraftIssue { assignees(first: 10) { nodes { login } } } } } }";
+
+#[derive(Deserialize)]
+struct ProjectData {
+    viewer: Login,
+    /// Set when the project was given by node ID.
+    project: Option<ProjectNode>,
+    /// Set when the project was given by URL.
+    owner: Option<ProjectOwner>,
+}
+
+#[derive(Deserialize)]
+struct ProjectOwner {
+    project: Option<ProjectNode>,
+}
+
+#[derive(Deserialize)]
+struct ProjectNode {
+    /// Missing when the node ID belongs to something other than a project.
+    items: Option<ProjectItems>,
+}
+
+#[derive(Deserialize)]
+struct ProjectItems {
+    nodes: Vec<Option<ProjectItem>>,
+}
+
+#[derive(Deserialize)]
+struct ProjectItem {
+    status: Option<StatusValue>,
+    content: Option<ItemContent>,
+}
+
+#[derive(Deserialize)]
+struct StatusValue {
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ItemContent {
+    assignees: Option<Assignees>,
+}
+
+#[derive(Deserialize)]
+struct Assignees {
+    nodes: Vec<Option<Login>>,
+}
+
+#[derive(Deserialize)]
+struct Login {
+    login: String,
+}
+
+/// Query and variables for a project given as a node ID (`PVT_…`) or as its URL, e.g.
+/// `https://github.com/orgs/acme/projects/3`.
+fn project_query(project: &str) -> Result<(String, serde_json::Value), FetchError> {
+    let project = project.trim();
+    let Ok(url) = url::Url::parse(project) else {
+        let query = format!(
+            "query($id: ID!) {{ viewer {{ login }} \
+             project: node(id: $id) {{ ... on ProjectV2 {{ {PROJECT_ITEM_FIELDS} }} }} }}"
+        );
+        return Ok((query, serde_json::json!({ "id": project })));
+    };
+
+    let segments: Vec<&str> = url.path_segments().map(Iterator::collect).unwrap_or_default();
+    let (owner, login, number) = match segments.as_slice() {
+        ["orgs", login, "projects", number, ..] => ("organization", *login, *number),
+        ["users", login, "projects", number, ..] => ("user", *login, *number),
+        _ => {
+            return Err(FetchError::new(
+                ErrorKind::Other,
+                format!("Not a project URL: {project}"),
+            ))
+        }
+    };
+    let number: u32 = number.parse().map_err(|_| {
+        FetchError::new(ErrorKind::Other, format!("Not a project URL: {project}"))
+    })?;
+    let query = format!(
+        "query($login: String!) {{ viewer {{ login }} \
+         owner: {owner}(login: $login) {{ \
+         project: projectV2(number: {number}) {{ {PROJECT_ITEM_FIELDS} }} }} }}"
+    );
+    Ok((query, serde_json::json!({ "login": login })))
+}
+
+/// Counts the items of a Projects (v2) board that are assigned to me, leaving out those
+/// whose Status is `ignored_status` (empty = count every status). Only the first 100 items
+/// of the board are looked at.
+pub async fn fetch_project_count(
+    request: &SearchRequest,
+    project: &str,
+    ignored_status: &str,
+) -> Result<u64, FetchError> {
+    let (query, variables) = project_query(project)?;
+    let data: ProjectData = graphql(request, &query, &variables).await?;
+
+    let items = data
+        .project
+        .or(data.owner.and_then(|owner| owner.project))
+        .and_then(|project| project.items)
+        .ok_or_else(|| {
+            FetchError::new(ErrorKind::Other, format!("Project not found: {}", project.trim()))
+        })?;
+    let me = data.viewer.login;
+
+    let count = items
+        .nodes
+        .into_iter()
+        .flatten()
+        .filter(|item| {
+            let status = item.status.as_ref().and_then(|status| status.name.as_deref());
+            ignored_status.is_empty() || status != Some(ignored_status)
+        })
+        .filter(|item| {
+            item.content
+                .as_ref()
+                .and_then(|content| content.assignees.as_ref())
+                .is_some_and(|assignees| {
+                    assignees.nodes.iter().flatten().any(|assignee| assignee.login == me)
+                })
+        })
+        .count();
+    Ok(count as u64)
+}
+
+/// Runs a GraphQL query with string or string-list `variables` and returns its `data`.
+async fn graphql<T: serde::de::DeserializeOwned>(
+    request: &SearchRequest,
+    query: &str,
+    variables: &serde_json::Value,
+) -> Result<T, FetchError> {
+    let body = match request.auth_method {
+        AuthMethod::GhCli => {
+            let mut command = tokio::process::Command::new("gh");
+            command.args(["api", "graphql", "-f", &format!("query={query}")]);
+            for (name, value) in variables.as_object().into_iter().flatten() {
+                match value {
+                    serde_json::Value::Array(items) => {
+                        for item in items.iter().filter_map(serde_json::Value::as_str) {
+                            command.args(["-f", &format!("{name}[]={item}")]);
+                        }
+                    }
+                    value => {
+                        let value = value.as_str().unwrap_or_default();
+                        command.args(["-f", &format!("{name}={value}")]);
+                    }
+                }
+            }
+            let output = command
+                .output()
+                .await
+                .map_err(|e| FetchError::new(ErrorKind::Network, format!("gh not found: {e}")))?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(FetchError::classify(stderr.trim().to_string()).with_details(&stderr));
+            }
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        }
+        AuthMethod::Pat => {
+            let pat = if request.borrows_token() {
+                borrowed_token(request, false).await?
+            } else {
+                request.pat.clone()
+            };
+            let payload = serde_json::json!({ "query": query, "variables": variables });
+            post_graphql(request, &pat, &payload.to_string())
+                .await
+                .map_err(|err| err.redact(&pat))?
+        }
+    };
+
+    let response: GraphQlResponse<T> = serde_json::from_str(&body).map_err(|e| {
+        FetchError::new(ErrorKind::Parse, format!("JSON parse error: {e}")).with_details(&body)
+    })?;
+    response.data.ok_or_else(|| {
+        let message = response
+            .errors
+            .into_iter()
+            .next()
+            .map(|error| error.message)
+            .or(response.message)
+            .unwrap_or_else(|| "data not found in response".to_string());
+        FetchError::classify(format!("API error: {message}")).with_details(&body)
+    })
+}
+
+/// Sends a GraphQL request body and returns the response body.
+#[cfg(feature = "reqwest-backend")]
+async fn post_graphql(
+    request: &SearchRequest,
+    pat: &str,
+    payload: &str,
+) -> Result<String, FetchError> {
+    let response = http_client(&request.tls)?
+        .post(format!("{API_BASE_URL}/graphql"))
+        .bearer_auth(pat)
+        .header("Content-Type", "application/json")
+        .body(payload.to_string())
+        .send()
+        .await
+        .map_err(FetchError::from_reqwest)?;
+    response.text().await.map_err(FetchError::from_reqwest)
+}
+
+/// Sends a GraphQL request body and returns the response body.
+#[cfg(all(feature = "curl-backend", not(feature = "reqwest-backend")))]
+async fn post_graphql(
+    request: &SearchRequest,
+    pat: &str,
+    payload: &str,
+) -> Result<String, FetchError> {
+    let mut command = tokio::process::Command::new("curl");
+    command.args([
+        "--silent",
+        "--show-error",
+        "-X", "POST",
+        "-H", &format!("Authorization: Bearer {pat}"),
+        "-H", "Content-Type: application/json",
+        "--data", payload,
+    ]);
+    if request.tls.allow_insecure {
+        command.arg("--insecure");
+    }
+    // Each curl run is a connection of its own, so there is no reuse to turn off.
+    if request.tls.http1_only {
+        command.arg("--http1.1");
+    }
+    if let Some(path) = &request.tls.custom_ca_path {
+        command.arg("--cacert").arg(path);
+    }
+
+    let output = command
+        .arg(format!("{API_BASE_URL}/graphql"))
+        .output()
+        .await
+        .map_err(|e| FetchError::new(ErrorKind::Network, format!("curl not found: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(FetchError::new(ErrorKind::Network, format!("Request failed: {}", stderr.trim()))
+            .with_details(&stderr));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Browser URL listing the PRs matched by `query` on the web host behind `api_base`.
+///
+/// github.com serves its API from `api.github.com`, while GitHub Enterprise Server serves it
+/// from `https://<host>/api/v3`.
+pub fn web_search_url(api_base: &str, query: &str) -> String {
+    let api_base = api_base.trim_end_matches('/');
+    let web_base = if api_base == API_BASE_URL {
+        "https://github.com"
+    } else {
+        api_base.strip_suffix("/api/v3").unwrap_or(api_base)
+    };
+
+    let encoded: String = url::form_urlencoded::byte_serialize(query.as_bytes()).collect();
+    format!("{web_base}/pulls?q={encoded}")
+}
+
+pub async fn check_gh_status() -> Result<String, String> {
+    let output = tokio::process::Command::new("gh")
+        .args(["auth", "status"])
+        .output()
+        .await
+        .map_err(|_| "gh not found or not executable".to_string())?;
+
+    // gh auth status writes to stderr
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    for line in text.lines() {
+        if line.contains("Logged in to") && line.contains("account") {
+            if let Some(pos) = line.find("account ") {
+                let rest = &line[pos + 8..];
+                let username = rest.split_whitespace().next().unwrap_or("unknown");
+                return Ok(username.to_string());
+            }
+        }
+    }
+
+    if !output.status.success() {
+        return Err("Not logged in. Run: gh auth login".to_string());
+    }
+
+    Ok("Connected".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gh_error_payload_surfaces_its_message() {
+        // What `gh api` prints for a rejected token.
+        let body = concat!(
+            r#"{"message":"Bad credentials","#,
+            r#""documentation_url":"https://docs.github.com/rest","status":"401"}"#
+        );
+        let err = parse_search_response(body).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Unauthorized);
+        assert_eq!(err.to_string(), "API error: Bad credentials");
+        assert_eq!(err.details.as_deref(), Some(body));
+    }
+
+    #[test]
+    fn gh_rate_limit_payload_is_classified() {
+        let body = r#"{"message":"API rate limit exceeded for user ID 1."}"#;
+        let err = parse_search_response(body).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::RateLimited);
+    }
+
+    #[test]
+    fn search_payload_parses_the_count() {
+        let body = r#"{"total_count":7,"incomplete_results":false,"items":[]}"#;
+        let results = parse_search_response(body).unwrap();
+        assert_eq!(results.total_count, 7);
+        assert!(!results.incomplete);
+    }
+
+    #[test]
+    fn payload_without_count_or_message_is_a_parse_error() {
+        let err = parse_search_response("{}").unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Parse);
+        let err = parse_search_response("not json").unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Parse);
+    }
+}
@@ -1,10 +1,34 @@
 // SPDX-License-Identifier: GPL-3.0
 
 mod app;
+mod backends;
+mod cache;
+mod cli;
 mod config;
+mod diagnostics;
+mod github;
 mod i18n;
+mod realtime;
+mod systemd;
 
 fn main() -> cosmic::iced::Result {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let has_flag = |flag: &str| args.iter().any(|arg| arg == flag);
+
+    // Before anything fetches, including the headless modes below.
+    backends::register_all();
+
+    // Headless mode: fetch once using the saved config and exit without starting the GUI.
+    if has_flag("--once") {
+        std::process::exit(cli::once(has_flag("--json")));
+    }
+    if has_flag("--check") {
+        std::process::exit(cli::check());
+    }
+    if has_flag("--status") {
+        std::process::exit(cli::status());
+    }
+
     // Get the system's preferred languages.
     let requested_languages = i18n_embed::DesktopLanguageRequester::requested_languages();
 
@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: GPL-3.0
+
+//! Push updates from a user-run relay that forwards GitHub webhooks as Server-Sent
+//! Events. Each event only means "something changed"; the applet then fetches as usual,
+//! so polling keeps working whenever the relay is unreachable.
+
+/// Connects to the relay and calls `on_event` for every event until the stream ends.
+#[cfg(feature = "reqwest-backend")]
+pub async fn listen(url: &str, mut on_event: impl FnMut()) -> Result<(), String> {
+    // No timeout: the stream is meant to stay open.
+    let mut response = reqwest::Client::new()
+        .get(url)
+        .header("Accept", "text/event-stream")
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| e.to_string())?;
+
+    let mut parser = EventParser::default();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        parser.feed(&String::from_utf8_lossy(&chunk), &mut on_event);
+    }
+    Ok(())
+}
+
+/// Connects to the relay and calls `on_event` for every event until the stream ends.
+#[cfg(all(feature = "curl-backend", not(feature = "reqwest-backend")))]
+pub async fn listen(url: &str, mut on_event: impl FnMut()) -> Result<(), String> {
+    use tokio::io::AsyncReadExt;
+
+    let mut child = tokio::process::Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--no-buffer"])
+        .args(["-H", "Accept: text/event-stream", url])
+        .stdout(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("curl not found: {e}"))?;
+    let mut stdout = child.stdout.take().ok_or("curl has no output")?;
+
+    let mut parser = EventParser::default();
+    let mut chunk = [0; 4096];
+    loop {
+        let len = stdout.read(&mut chunk).await.map_err(|e| e.to_string())?;
+        if len == 0 {
+            return Ok(());
+        }
+        parser.feed(&String::from_utf8_lossy(&chunk[..len]), &mut on_event);
+    }
+}
+
+/// Splits an SSE stream into events. Comment lines (keep-alives) don't count.
+#[derive(Default)]
+struct EventParser {
+    /// Text after the last complete line.
+    partial: String,
+    /// The event being read has at least one field.
+    has_fields: bool,
+}
+
+impl EventParser {
+    fn feed(&mut self, text: &str, on_event: &mut impl FnMut()) {
+        self.partial.push_str(text);
+        while let Some(end) = self.partial.find('\n') {
+            let line: String = self.partial.drain(..=end).collect();
+            self.line(line.trim_end_matches(['\r', '\n']), on_event);
+        }
+    }
+
+    /// Handles one line; a blank line completes the event.
+    fn line(&mut self, line: &str, on_event: &mut impl FnMut()) {
+        if line.is_empty() {
+            if std::mem::take(&mut self.has_fields) {
+                on_event();
+            }
+        } else if !line.starts_with(':') {
+            self.has_fields = true;
+        }
+    }
+}
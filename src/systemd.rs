@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: GPL-3.0
+
+//! Readiness notification for systemd units, speaking the `sd_notify` protocol directly.
+
+use std::os::unix::net::{SocketAddr, UnixDatagram};
+
+/// Tells systemd the applet is up, when it was started by a unit with `Type=notify`.
+/// Does nothing outside systemd; failures are only logged.
+pub fn notify_ready() {
+    let Some(socket_path) = std::env::var_os("NOTIFY_SOCKET") else {
+        return;
+    };
+    if let Err(why) = send(&socket_path, b"READY=1") {
+        eprintln!("failed to notify systemd: {why}");
+    }
+}
+
+fn send(socket_path: &std::ffi::OsStr, message: &[u8]) -> std::io::Result<()> {
+    use std::os::unix::ffi::OsStrExt;
+
+    // A leading '@' names a socket in the abstract namespace.
+    let addr = match socket_path.as_bytes().strip_prefix(b"@") {
+        Some(name) => {
+            use std::os::linux::net::SocketAddrExt;
+            SocketAddr::from_abstract_name(name)?
+        }
+        None => SocketAddr::from_pathname(socket_path)?,
+    };
+    UnixDatagram::unbound()?.send_to_addr(message, &addr)?;
+    Ok(())
+}